@@ -0,0 +1,43 @@
+use std::fs;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc::day3::*;
+
+pub fn day3_bench(c: &mut Criterion) {
+    let input = fs::read_to_string("./input/day3.txt").expect("failed to read input file");
+    let input: Input = input.parse().expect("failed to parse input file");
+
+    let mut group = c.benchmark_group("ratings");
+    group.bench_with_input("split_by_bit", &input, |b, input| {
+        b.iter(|| {
+            (
+                filter_by_bit_criteria(input, BitCriteria::MostCommon, TieBreak::PreferOne),
+                filter_by_bit_criteria(input, BitCriteria::LeastCommon, TieBreak::PreferZero),
+            )
+        })
+    });
+    group.bench_with_input("trie", &input, |b, input| {
+        b.iter(|| {
+            let trie = BitTrie::new(input);
+            (
+                trie.filter(BitCriteria::MostCommon, TieBreak::PreferOne),
+                trie.filter(BitCriteria::LeastCommon, TieBreak::PreferZero),
+            )
+        })
+    });
+    // Without building the trie:
+    let trie = BitTrie::new(&input);
+    group.bench_with_input("trie_walk", &trie, |b, trie| {
+        b.iter(|| {
+            (
+                trie.filter(BitCriteria::MostCommon, TieBreak::PreferOne),
+                trie.filter(BitCriteria::LeastCommon, TieBreak::PreferZero),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, day3_bench);
+criterion_main!(benches);
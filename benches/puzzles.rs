@@ -0,0 +1,40 @@
+use std::fs;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc::registry::{Part, PUZZLES};
+
+/// Benchmarks parsing and both parts of every puzzle in the registry.
+///
+/// Note that the parts are measured including the conversion of their answer to a string.
+pub fn puzzles_bench(c: &mut Criterion) {
+    for puzzle in PUZZLES {
+        let path = puzzle.default_input_path();
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!(
+                    "skipping day {}: failed to read {}: {}",
+                    puzzle.day, path, e
+                );
+                continue;
+            }
+        };
+        let parsed = puzzle.parse(&input).expect("failed to parse input file");
+
+        let mut group = c.benchmark_group(format!("day{}", puzzle.day));
+        group.bench_with_input("parse", &input, |b, input| b.iter(|| puzzle.parse(input)));
+        for (name, part) in [("part1", Part::One), ("part2", Part::Two)] {
+            puzzle
+                .solve_parsed(part, &parsed)
+                .unwrap_or_else(|e| panic!("failed to solve day {} {}: {}", puzzle.day, name, e));
+            group.bench_with_input(name, &parsed, |b, input| {
+                b.iter(|| puzzle.solve_parsed(part, input))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, puzzles_bench);
+criterion_main!(benches);
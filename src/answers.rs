@@ -0,0 +1,214 @@
+//! Known answers for the real puzzle inputs, to catch regressions end-to-end.
+
+use std::{fmt, fs, path::Path};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::registry::{Part, Puzzle};
+
+/// Where the answers are recorded by default, relative to the repository root.
+pub const DEFAULT_PATH: &str = "./answers.toml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "puzzle")]
+    pub puzzles: Vec<PuzzleAnswers>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleAnswers {
+    pub day: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read answers file {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse answers file {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("failed to write answers file {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        let answers = self.puzzles.iter().find(|answers| answers.day == day)?;
+        match part {
+            Part::One => answers.part1.as_deref(),
+            Part::Two => answers.part2.as_deref(),
+        }
+    }
+
+    /// Solves the given puzzles on their default input files and records the answers.
+    ///
+    /// Fails if any part cannot be solved, so a broken part never loses its recorded answer.
+    pub fn record(puzzles: &[Puzzle]) -> anyhow::Result<Self> {
+        let puzzles = puzzles
+            .iter()
+            .map(|puzzle| {
+                let input = read_input(puzzle)?;
+                let parsed = puzzle.parse(&input)?;
+                let solve = |part| {
+                    puzzle.solve_parsed(part, &parsed).with_context(|| {
+                        format!("failed to solve day {} part {}", puzzle.day, part)
+                    })
+                };
+                Ok(PuzzleAnswers {
+                    day: puzzle.day,
+                    part1: Some(solve(Part::One)?),
+                    part2: Some(solve(Part::Two)?),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self { puzzles })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer matches the recorded one.
+    Unchanged,
+    Changed {
+        expected: String,
+        actual: String,
+    },
+    /// There is a recorded answer, but solving the puzzle failed.
+    Failed {
+        expected: String,
+        error: String,
+    },
+    /// There is no recorded answer to compare to.
+    Unrecorded,
+}
+
+impl Outcome {
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Outcome::Changed { .. } | Outcome::Failed { .. })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Unchanged => write!(f, "ok"),
+            Outcome::Changed { expected, actual } => {
+                write!(f, "CHANGED: expected {}, got {}", expected, actual)
+            }
+            Outcome::Failed { expected, error } => {
+                write!(f, "FAILED: expected {}, got error: {}", expected, error)
+            }
+            Outcome::Unrecorded => write!(f, "no recorded answer"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+/// Solves the given puzzles on their default input files and compares the answers to the
+/// recorded ones.
+pub fn check(puzzles: &[Puzzle], answers: &Answers) -> anyhow::Result<Vec<Check>> {
+    let mut checks = vec![];
+    for puzzle in puzzles {
+        let input = read_input(puzzle)?;
+        let parsed = puzzle.parse(&input);
+        for part in [Part::One, Part::Two] {
+            let actual = match &parsed {
+                Ok(parsed) => puzzle.solve_parsed(part, parsed),
+                Err(e) => Err(anyhow::anyhow!("{:#}", e)),
+            };
+            let outcome = match (answers.get(puzzle.day, part), actual) {
+                (None, _) => Outcome::Unrecorded,
+                (Some(expected), Ok(actual)) if expected == actual => Outcome::Unchanged,
+                (Some(expected), Ok(actual)) => Outcome::Changed {
+                    expected: expected.to_owned(),
+                    actual,
+                },
+                (Some(expected), Err(e)) => Outcome::Failed {
+                    expected: expected.to_owned(),
+                    error: format!("{:#}", e),
+                },
+            };
+            checks.push(Check {
+                day: puzzle.day,
+                part,
+                outcome,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+fn read_input(puzzle: &Puzzle) -> anyhow::Result<String> {
+    let path = puzzle.default_input_path();
+    fs::read_to_string(&path).with_context(|| format!("failed to read input file {}", path))
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::bail;
+
+    use super::*;
+    use crate::Solution;
+
+    /// Solves part 1 of any input, but fails on part 2.
+    struct HalfSolved;
+
+    impl Solution for HalfSolved {
+        type Input = ();
+        type Answer1 = u8;
+        type Answer2 = u8;
+
+        fn parse(_input: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> anyhow::Result<u8> {
+            Ok(1)
+        }
+
+        fn part2(_input: &()) -> anyhow::Result<u8> {
+            bail!("not solved yet")
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let answers = Answers {
+            puzzles: vec![PuzzleAnswers {
+                day: 2,
+                part1: None,
+                part2: Some("900".to_owned()),
+            }],
+        };
+        let toml = toml::to_string(&answers).unwrap();
+        assert_eq!(toml, "[[puzzle]]\nday = 2\npart2 = \"900\"\n");
+        let answers: Answers = toml::from_str(&toml).unwrap();
+        assert_eq!(answers.get(2, Part::One), None);
+        assert_eq!(answers.get(2, Part::Two), Some("900"));
+        assert_eq!(answers.get(3, Part::Two), None);
+    }
+
+    #[test]
+    fn test_record_fails_on_unsolved_parts() {
+        let puzzles = [Puzzle::new::<HalfSolved>(1, "Half Solved")];
+        let err = Answers::record(&puzzles).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "failed to solve day 1 part 2: not solved yet"
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use std::{
+    cmp::Ordering, collections::VecDeque, fmt, io::BufRead, ops::RangeInclusive, str::FromStr,
+};
+
+use anyhow::bail;
+
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> anyhow::Result<usize> {
+        Ok(measure_looking_at_individual_numbers(nums))
+    }
+
+    fn part2(nums: &Self::Input) -> anyhow::Result<usize> {
+        Ok(measure_looking_at_sliding_windows_of_three(nums))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    numbered_lines(input)
+        .map(|(line_no, line)| parse::number(line_no, line, line.trim()))
+        .collect()
+}
+
+pub fn measure_looking_at_individual_numbers(nums: &[i32]) -> usize {
+    nums.windows(2).filter(|pair| pair[1] > pair[0]).count()
+}
+
+pub fn measure_looking_at_sliding_windows_of_three(nums: &[i32]) -> usize {
+    count_increases(nums.iter().copied(), 3)
+}
+
+/// Counts how often the sum of a sliding window of depths increases, while only keeping
+/// the current window in memory.
+///
+/// Two consecutive windows share all but their first and last depth, so the sum
+/// increases exactly if the depth entering the window is larger than the one leaving it.
+pub struct IncreaseCounter {
+    window: VecDeque<i32>,
+    window_size: usize,
+    n_increases: usize,
+}
+
+impl IncreaseCounter {
+    pub fn new(window_size: usize) -> Self {
+        assert!(window_size > 0, "the window must hold at least one depth");
+        Self {
+            window: VecDeque::with_capacity(window_size),
+            window_size,
+            n_increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i32) {
+        if self.window.len() == self.window_size {
+            let leaving = self.window.pop_front().unwrap();
+            if depth > leaving {
+                self.n_increases += 1;
+            }
+        }
+        self.window.push_back(depth);
+    }
+
+    pub fn increases(&self) -> usize {
+        self.n_increases
+    }
+}
+
+pub fn count_increases(depths: impl IntoIterator<Item = i32>, window_size: usize) -> usize {
+    let mut counter = IncreaseCounter::new(window_size);
+    for depth in depths {
+        counter.push(depth);
+    }
+    counter.increases()
+}
+
+/// Like count_increases, but reads the depths line by line, so the input doesn't have to
+/// fit into memory.
+pub fn count_increases_in(reader: impl BufRead, window_size: usize) -> anyhow::Result<usize> {
+    let mut counter = IncreaseCounter::new(window_size);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        counter.push(parse::number(i + 1, &line, line.trim())?);
+    }
+    Ok(counter.increases())
+}
+
+/// How the depths within a window are combined before comparing windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    /// Windows have the same size, so this compares exactly like `Sum`.
+    Mean,
+    Max,
+}
+
+impl Aggregate {
+    fn compare(self, a: &[i32], b: &[i32]) -> Ordering {
+        match self {
+            Aggregate::Sum | Aggregate::Mean => {
+                let sum = |window: &[i32]| window.iter().map(|&x| x as i64).sum::<i64>();
+                sum(a).cmp(&sum(b))
+            }
+            Aggregate::Max => a.iter().max().cmp(&b.iter().max()),
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "mean" => Ok(Aggregate::Mean),
+            "max" => Ok(Aggregate::Max),
+            _ => bail!("unknown aggregate `{}`", s),
+        }
+    }
+}
+
+/// How a window compares to the window before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Increase,
+    Decrease,
+    Unchanged,
+}
+
+impl FromStr for Comparison {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "increase" => Ok(Comparison::Increase),
+            "decrease" => Ok(Comparison::Decrease),
+            "unchanged" => Ok(Comparison::Unchanged),
+            _ => bail!("unknown comparison `{}`", s),
+        }
+    }
+}
+
+/// The number of windows that increased, decreased or stayed unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub increased: usize,
+    pub decreased: usize,
+    pub unchanged: usize,
+}
+
+impl Measurement {
+    pub fn count(&self, comparison: Comparison) -> usize {
+        match comparison {
+            Comparison::Increase => self.increased,
+            Comparison::Decrease => self.decreased,
+            Comparison::Unchanged => self.unchanged,
+        }
+    }
+}
+
+/// Compares each sliding window of `window_size` depths to the window before it.
+///
+/// Part 1 counts the increases for a window size of 1, part 2 for a window size of 3.
+pub fn measure(nums: &[i32], window_size: usize, aggregate: Aggregate) -> Measurement {
+    assert!(window_size > 0, "the window must hold at least one depth");
+    let mut measurement = Measurement::default();
+    let windows = nums.windows(window_size);
+    for (prev, next) in windows.clone().zip(windows.skip(1)) {
+        match aggregate.compare(next, prev) {
+            Ordering::Greater => measurement.increased += 1,
+            Ordering::Less => measurement.decreased += 1,
+            Ordering::Equal => measurement.unchanged += 1,
+        }
+    }
+    measurement
+}
+
+/// Statistics over the depths of a sonar sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthReport {
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    /// The indices of the longest run of strictly increasing depths; the first one wins.
+    pub longest_increasing_run: RangeInclusive<usize>,
+    /// The largest difference between two consecutive depths, if there are at least two.
+    pub largest_jump: Option<Jump>,
+    pub spikes: Vec<Spike>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    /// The index of the depth after the jump.
+    pub index: usize,
+    pub from: i32,
+    pub to: i32,
+}
+
+/// A depth that deviates from the mean of the depths before it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spike {
+    pub index: usize,
+    pub depth: i32,
+    pub rolling_mean: f64,
+    /// The deviation from the rolling mean, in standard deviations of the rolling window.
+    pub deviations: f64,
+}
+
+/// Computes the statistics of the given depths; `None` if there are none.
+///
+/// A depth is a spike if it deviates from the mean of the `window_size` depths before it
+/// by more than `threshold` standard deviations of those depths.
+pub fn depth_report(nums: &[i32], window_size: usize, threshold: f64) -> Option<DepthReport> {
+    assert!(window_size > 0, "the window must hold at least one depth");
+    let min = *nums.iter().min()?;
+    let max = *nums.iter().max()?;
+    let mean = nums.iter().map(|&x| x as f64).sum::<f64>() / nums.len() as f64;
+
+    let mut longest_increasing_run = 0..=0;
+    let mut run_start = 0;
+    for (i, pair) in nums.windows(2).enumerate() {
+        if pair[1] <= pair[0] {
+            run_start = i + 1;
+        } else if i + 1 - run_start > longest_increasing_run.end() - longest_increasing_run.start()
+        {
+            longest_increasing_run = run_start..=i + 1;
+        }
+    }
+
+    let largest_jump = nums
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| Jump {
+            index: i + 1,
+            from: pair[0],
+            to: pair[1],
+        })
+        // `max_by_key` would return the last of several equal jumps:
+        .rev()
+        .max_by_key(|jump| jump.from.abs_diff(jump.to));
+
+    let spikes = nums
+        .windows(window_size + 1)
+        .enumerate()
+        .filter_map(|(i, window)| {
+            let (&depth, before) = window.split_last().unwrap();
+            let rolling_mean = before.iter().map(|&x| x as f64).sum::<f64>() / window_size as f64;
+            let variance = before
+                .iter()
+                .map(|&x| (x as f64 - rolling_mean).powi(2))
+                .sum::<f64>()
+                / window_size as f64;
+            // A deviation from a constant window is infinitely many standard deviations:
+            let deviations = (depth as f64 - rolling_mean).abs() / variance.sqrt();
+            (deviations > threshold).then(|| Spike {
+                index: i + window_size,
+                depth,
+                rolling_mean,
+                deviations,
+            })
+        })
+        .collect();
+
+    Some(DepthReport {
+        min,
+        max,
+        mean,
+        longest_increasing_run,
+        largest_jump,
+        spikes,
+    })
+}
+
+impl fmt::Display for DepthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "min depth: {}", self.min)?;
+        writeln!(f, "max depth: {}", self.max)?;
+        writeln!(f, "mean depth: {:.2}", self.mean)?;
+        let run = &self.longest_increasing_run;
+        writeln!(
+            f,
+            "longest increasing run: {} depths, from index {} to {}",
+            run.end() - run.start() + 1,
+            run.start(),
+            run.end()
+        )?;
+        if let Some(jump) = self.largest_jump {
+            writeln!(
+                f,
+                "largest jump: {} -> {} at index {}",
+                jump.from, jump.to, jump.index
+            )?;
+        }
+        write!(f, "spikes: {}", self.spikes.len())?;
+        for spike in &self.spikes {
+            write!(
+                f,
+                "\n  index {}: {} (rolling mean {:.2}, {:.1} standard deviations)",
+                spike.index, spike.depth, spike.rolling_mean, spike.deviations
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_measuring_by_looking_at_individual_numbers() {
+    let input = "\
+        199
+        200
+        208
+        210
+        200
+        207
+        240
+        269
+        260
+        263
+        ";
+    let input = parse(input).unwrap();
+    let increases = measure_looking_at_individual_numbers(&input);
+    assert_eq!(increases, 7);
+}
+
+#[test]
+fn test_measuring_by_looking_at_sliding_windows_of_three() {
+    let input = "\
+        199
+        200
+        208
+        210
+        200
+        207
+        240
+        269
+        260
+        263
+        ";
+    let input = parse(input).unwrap();
+    let increases = measure_looking_at_sliding_windows_of_three(&input);
+    assert_eq!(increases, 5);
+}
+
+#[test]
+fn test_parse_error_points_at_line() {
+    let err = parse("199\n200\n2o8\n").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    assert_eq!(err.text, "2o8");
+}
+
+#[test]
+fn test_count_increases_in_reader() {
+    let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+    assert_eq!(count_increases_in(input.as_bytes(), 1).unwrap(), 7);
+    assert_eq!(count_increases_in(input.as_bytes(), 3).unwrap(), 5);
+    // No two windows fit into the input:
+    assert_eq!(count_increases_in(input.as_bytes(), 10).unwrap(), 0);
+}
+
+#[test]
+fn test_count_increases_in_reader_reports_line() {
+    let err = count_increases_in("199\n\n2o8\n".as_bytes(), 1).unwrap_err();
+    let err = err.downcast::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (3, 1));
+}
+
+#[test]
+fn test_measure() {
+    let input = parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+    let measurement = measure(&input, 1, Aggregate::Sum);
+    assert_eq!(
+        measurement,
+        Measurement {
+            increased: 7,
+            decreased: 2,
+            unchanged: 0
+        }
+    );
+    let measurement = measure(&input, 3, Aggregate::Mean);
+    assert_eq!(measurement.count(Comparison::Increase), 5);
+    assert_eq!(measurement.count(Comparison::Unchanged), 1);
+    // The window maxima are 208, 210, 210, 210, 240, 269, 269, 269:
+    let measurement = measure(&input, 3, Aggregate::Max);
+    assert_eq!(
+        measurement,
+        Measurement {
+            increased: 3,
+            decreased: 0,
+            unchanged: 4
+        }
+    );
+}
+
+#[test]
+fn test_depth_report() {
+    let input = parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+    let report = depth_report(&input, 3, 2.0).unwrap();
+    assert_eq!((report.min, report.max), (199, 269));
+    assert!((report.mean - 225.6).abs() < 1e-9);
+    assert_eq!(report.longest_increasing_run, 0..=3);
+    assert_eq!(
+        report.largest_jump,
+        Some(Jump {
+            index: 6,
+            from: 207,
+            to: 240
+        })
+    );
+    let spikes: Vec<_> = report.spikes.iter().map(|s| (s.index, s.depth)).collect();
+    assert_eq!(spikes, vec![(6, 240), (7, 269)]);
+}
+
+#[test]
+fn test_depth_report_without_depths() {
+    assert_eq!(depth_report(&[], 3, 2.0), None);
+}
@@ -1,15 +1,32 @@
-use std::fs;
-
-fn main() {
-    let input = fs::read_to_string("./input/day10.txt").expect("failed to read input file");
-    let score = score_for(&syntax_errors_in(&input));
-    println!("part 1 syntax error score: {}", score);
-    let incomplete_lines = incomplete_lines_in(&input);
-    let completion_score = completion_score_of(&incomplete_lines);
-    println!("part 2 completion score: {}", completion_score);
+use anyhow::bail;
+
+use crate::{parse::numbered_lines, ParseError, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(lines: &Self::Input) -> anyhow::Result<usize> {
+        Ok(score_for(&syntax_errors_in(lines)))
+    }
+
+    fn part2(lines: &Self::Input) -> anyhow::Result<usize> {
+        let incomplete_lines = incomplete_lines_in(lines);
+        if incomplete_lines.is_empty() {
+            bail!("no line is incomplete");
+        }
+        Ok(completion_score_of(&incomplete_lines))
+    }
 }
 
-enum ParseLineResult<'a> {
+pub enum ParseLineResult<'a> {
     Ok,
     Incomplete(IncompleteLine<'a>),
     SyntaxError(SyntaxError),
@@ -27,25 +44,21 @@ impl<'a> ParseLineResult<'a> {
     }
 }
 
-struct SyntaxError {
-    #[allow(dead_code)]
-    pos: usize,
-    #[allow(dead_code)]
-    expected: char,
-    actual: Option<char>,
+pub struct SyntaxError {
+    pub pos: usize,
+    pub expected: char,
+    pub actual: Option<char>,
 }
 
-struct IncompleteLine<'a> {
-    #[allow(dead_code)]
-    line: &'a str,
-    open_symbols: Vec<char>,
+pub struct IncompleteLine<'a> {
+    pub line: &'a str,
+    pub open_symbols: Vec<char>,
 }
 impl<'a> IncompleteLine<'a> {
-    #[allow(dead_code)]
-    fn completed(&self) -> String {
+    pub fn completed(&self) -> String {
         format!("{}{}", self.line, self.completion())
     }
-    fn score(&self) -> usize {
+    pub fn score(&self) -> usize {
         let mut score = 0;
         for c in self.completion().chars() {
             // For each character, multiply the total score by 5..
@@ -61,7 +74,7 @@ impl<'a> IncompleteLine<'a> {
         }
         score
     }
-    fn completion(&self) -> String {
+    pub fn completion(&self) -> String {
         self.open_symbols
             .iter()
             .rev()
@@ -70,7 +83,7 @@ impl<'a> IncompleteLine<'a> {
     }
 }
 
-fn parse_line(line: &str) -> ParseLineResult {
+pub fn parse_line(line: &str) -> ParseLineResult<'_> {
     let mut stack = vec![];
     for (pos, symbol) in line.chars().enumerate() {
         match symbol {
@@ -118,10 +131,30 @@ fn closing_symbol_for(symbol: char) -> Option<char> {
     }
 }
 
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    numbered_lines(input)
+        .map(|(line_no, line)| {
+            let chunks = line.trim();
+            match chunks
+                .char_indices()
+                .find(|&(_, c)| !"()[]{}<>".contains(c))
+            {
+                Some((pos, c)) => Err(ParseError::new(
+                    line_no,
+                    line,
+                    &chunks[pos..pos + c.len_utf8()],
+                    "expected one of `()[]{}<>`",
+                )),
+                None => Ok(chunks.to_owned()),
+            }
+        })
+        .collect()
+}
+
 // Part 1
-fn syntax_errors_in(text: &str) -> Vec<SyntaxError> {
-    text.lines()
-        .map(|l| l.trim())
+pub fn syntax_errors_in(lines: &[String]) -> Vec<SyntaxError> {
+    lines
+        .iter()
         .filter_map(|line| match parse_line(line) {
             ParseLineResult::SyntaxError(syntax_error) => Some(syntax_error),
             _ => None,
@@ -130,9 +163,9 @@ fn syntax_errors_in(text: &str) -> Vec<SyntaxError> {
 }
 
 // Part 2
-fn incomplete_lines_in(text: &str) -> Vec<IncompleteLine> {
-    text.lines()
-        .map(|l| l.trim())
+pub fn incomplete_lines_in(lines: &[String]) -> Vec<IncompleteLine<'_>> {
+    lines
+        .iter()
         .filter_map(|line| match parse_line(line) {
             ParseLineResult::Incomplete(incomplete_line) => Some(incomplete_line),
             _ => None,
@@ -141,7 +174,7 @@ fn incomplete_lines_in(text: &str) -> Vec<IncompleteLine> {
 }
 
 // Part 1
-fn score_for(syntax_errors: &[SyntaxError]) -> usize {
+pub fn score_for(syntax_errors: &[SyntaxError]) -> usize {
     syntax_errors
         .iter()
         .map(|e| e.actual)
@@ -155,8 +188,8 @@ fn score_for(syntax_errors: &[SyntaxError]) -> usize {
         .sum()
 }
 
-// Part 2
-fn completion_score_of(incomplete_lines: &[IncompleteLine]) -> usize {
+// Part 2; expects at least one incomplete line.
+pub fn completion_score_of(incomplete_lines: &[IncompleteLine]) -> usize {
     // We need to sort all the scores, and then select the middle score.
     let mut scores: Vec<usize> = incomplete_lines.iter().map(|x| x.score()).collect();
     scores.sort_unstable();
@@ -182,7 +215,7 @@ mod test {
 
     #[test]
     fn test_part1_syntax_errors() {
-        let score = score_for(&syntax_errors_in(INPUT));
+        let score = score_for(&syntax_errors_in(&parse(INPUT).unwrap()));
         assert_eq!(score, 26397);
     }
 
@@ -201,7 +234,8 @@ mod test {
             .filter(|x| !x.is_empty())
             .collect();
 
-        let incomplete_lines = incomplete_lines_in(INPUT);
+        let lines = parse(INPUT).unwrap();
+        let incomplete_lines = incomplete_lines_in(&lines);
         let completed_actual: Vec<String> =
             incomplete_lines.iter().map(|x| x.completed()).collect();
         assert_eq!(completed_actual, completed_expected);
@@ -209,4 +243,17 @@ mod test {
         let completion_score = completion_score_of(&incomplete_lines);
         assert_eq!(completion_score, 288957);
     }
+
+    #[test]
+    fn test_parse_rejects_other_characters() {
+        let err = parse("[({(<(())[]>[[{[]{<()<>>\n  [(a)]\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.text, "a");
+    }
+
+    #[test]
+    fn test_part2_without_incomplete_lines() {
+        let lines = Day10::parse("()\n{()()()>\n").unwrap();
+        assert!(Day10::part2(&lines).is_err());
+    }
 }
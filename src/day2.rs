@@ -0,0 +1,670 @@
+use std::{error::Error, fmt, io::Write, str::FromStr};
+
+use anyhow::{anyhow, bail};
+use serde::{Serialize, Serializer};
+
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Statement>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(commands: &Self::Input) -> anyhow::Result<i64> {
+        let Distance {
+            horizontal, depth, ..
+        } = calculate_distance(commands, MovementModel::Direct)?;
+        horizontal
+            .checked_mul(depth)
+            .ok_or_else(|| anyhow!("the answer is too large"))
+    }
+
+    fn part2(commands: &Self::Input) -> anyhow::Result<i64> {
+        let Distance {
+            horizontal, depth, ..
+        } = calculate_distance(commands, MovementModel::Aim)?;
+        horizontal
+            .checked_mul(depth)
+            .ok_or_else(|| anyhow!("the answer is too large"))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Distance {
+    pub horizontal: i64,
+    /// Never negative, as the submarine can't leave the water.
+    pub depth: i64,
+    /// Negative when aiming upwards.
+    pub aim: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Forward(u32),
+    Backward(u32),
+    Down(u32),
+    Up(u32),
+    /// Rises straight to the surface.
+    Surface,
+}
+
+impl Command {
+    pub fn from(line_no: usize, line: &str) -> Result<Self, ParseError> {
+        use Command::*;
+        let parts = line.split_whitespace().collect::<Vec<&str>>();
+        let (command, arg) = match parts[..] {
+            ["surface"] => return Ok(Surface),
+            [command, arg] if command != "surface" => (command, arg),
+            _ => {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    line.trim(),
+                    "expected a command followed by a number, or `surface`",
+                ))
+            }
+        };
+        let arg = parse::number(line_no, line, arg)?;
+        let command = match command {
+            "forward" => Forward(arg),
+            "backward" => Backward(arg),
+            "down" => Down(arg),
+            "up" => Up(arg),
+            _ => {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    command,
+                    "expected one of `forward`, `backward`, `down`, `up` or `surface`",
+                ))
+            }
+        };
+        Ok(command)
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Command::*;
+        match self {
+            Forward(units) => write!(f, "forward {}", units),
+            Backward(units) => write!(f, "backward {}", units),
+            Down(units) => write!(f, "down {}", units),
+            Up(units) => write!(f, "up {}", units),
+            Surface => write!(f, "surface"),
+        }
+    }
+}
+
+/// A command, or a block of statements that is repeated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Command(Command),
+    Repeat { times: u32, body: Vec<Statement> },
+}
+
+/// The most commands a course may expand to once its blocks are repeated.
+pub const MAX_COMMANDS: u64 = 100_000_000;
+
+/// Parses a course, which apart from one command per line may contain
+///
+/// - comments, starting with `#` and running until the end of the line, and
+/// - blocks of statements that are repeated, written as `repeat N {`, followed by the
+///   statements, followed by `}` on a line of its own. Blocks can be nested.
+///
+/// Courses that expand to more than MAX_COMMANDS commands are rejected.
+pub fn parse(input: &str) -> Result<Vec<Statement>, ParseError> {
+    // The blocks that are still open, together with the line that opened them and the
+    // statements and number of commands before them:
+    let mut open_blocks: Vec<(usize, &str, u32, Vec<Statement>, u64)> = Vec::new();
+    let mut statements = Vec::new();
+    // The number of commands the statements expand to:
+    let mut n_commands: u64 = 0;
+    for (line_no, line) in numbered_lines(input) {
+        let code = match line.find('#') {
+            Some(comment_start) => &line[..comment_start],
+            None => line,
+        };
+        let statement = match code.split_whitespace().collect::<Vec<&str>>()[..] {
+            [] => continue,
+            ["repeat", times, "{"] => {
+                let times = parse::number(line_no, line, times)?;
+                let body = std::mem::take(&mut statements);
+                open_blocks.push((line_no, line, times, body, n_commands));
+                n_commands = 0;
+                continue;
+            }
+            ["}"] => match open_blocks.pop() {
+                Some((repeat_line_no, repeat_line, times, outer, n_outer_commands)) => {
+                    n_commands = n_commands
+                        .checked_mul(times.into())
+                        .and_then(|n| n.checked_add(n_outer_commands))
+                        .filter(|&n| n <= MAX_COMMANDS)
+                        .ok_or_else(|| {
+                            ParseError::new(
+                                repeat_line_no,
+                                repeat_line,
+                                repeat_line.trim(),
+                                format!(
+                                    "the course expands to more than {} commands",
+                                    MAX_COMMANDS
+                                ),
+                            )
+                        })?;
+                    Statement::Repeat {
+                        times,
+                        body: std::mem::replace(&mut statements, outer),
+                    }
+                }
+                None => {
+                    return Err(ParseError::new(
+                        line_no,
+                        line,
+                        code.trim(),
+                        "no block to close",
+                    ))
+                }
+            },
+            _ => {
+                n_commands += 1;
+                Statement::Command(Command::from(line_no, code)?)
+            }
+        };
+        statements.push(statement);
+    }
+    match open_blocks.pop() {
+        Some((line_no, line, ..)) => Err(ParseError::new(
+            line_no,
+            line,
+            line.trim(),
+            "block is never closed",
+        )),
+        None => Ok(statements),
+    }
+}
+
+/// Iterates over the commands of the statements, repeating blocks as often as requested.
+pub fn commands(statements: &[Statement]) -> Commands<'_> {
+    Commands {
+        stack: vec![Block {
+            statements,
+            next: 0,
+            runs_left: 1,
+        }],
+    }
+}
+
+pub struct Commands<'a> {
+    /// The block that is currently run is on top; the blocks containing it are below.
+    stack: Vec<Block<'a>>,
+}
+
+struct Block<'a> {
+    statements: &'a [Statement],
+    next: usize,
+    /// Including the current run.
+    runs_left: u32,
+}
+
+impl<'a> Iterator for Commands<'a> {
+    type Item = &'a Command;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let block = self.stack.last_mut()?;
+            match block.statements.get(block.next) {
+                None if block.runs_left > 1 => {
+                    block.runs_left -= 1;
+                    block.next = 0;
+                }
+                None => {
+                    self.stack.pop();
+                }
+                Some(Statement::Command(command)) => {
+                    block.next += 1;
+                    return Some(command);
+                }
+                Some(Statement::Repeat { times, body }) => {
+                    block.next += 1;
+                    // Running an empty block any number of times does nothing:
+                    if *times > 0 && !body.is_empty() {
+                        self.stack.push(Block {
+                            statements: body,
+                            next: 0,
+                            runs_left: *times,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How the submarine interprets its commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementModel {
+    /// Part 1: `down` and `up` change the depth directly.
+    Direct,
+    /// Part 2: `down` and `up` change the aim, and `forward` also dives along the aim.
+    Aim,
+}
+
+impl FromStr for MovementModel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "direct" => Ok(MovementModel::Direct),
+            "aim" => Ok(MovementModel::Aim),
+            _ => bail!("unknown movement model `{}`", s),
+        }
+    }
+}
+
+impl MovementModel {
+    /// Moves according to the `step`-th command.
+    pub fn apply(
+        self,
+        step: usize,
+        distance: Distance,
+        command: &Command,
+    ) -> Result<Distance, NavigationError> {
+        use Command::*;
+        let Distance {
+            horizontal,
+            depth,
+            aim,
+        } = distance;
+        let overflow = || NavigationError::Overflow { step };
+        let distance = match (self, command) {
+            (_, Forward(units)) => Distance {
+                horizontal: horizontal
+                    .checked_add(i64::from(*units))
+                    .ok_or_else(overflow)?,
+                depth: aim
+                    .checked_mul(i64::from(*units))
+                    .and_then(|dive| depth.checked_add(dive))
+                    .ok_or_else(overflow)?,
+                ..distance
+            },
+            (_, Backward(units)) => Distance {
+                horizontal: horizontal
+                    .checked_sub(i64::from(*units))
+                    .ok_or_else(overflow)?,
+                ..distance
+            },
+            (_, Surface) => Distance {
+                depth: 0,
+                ..distance
+            },
+            (MovementModel::Direct, Down(units)) => Distance {
+                depth: depth.checked_add(i64::from(*units)).ok_or_else(overflow)?,
+                ..distance
+            },
+            (MovementModel::Direct, Up(units)) => Distance {
+                depth: depth.checked_sub(i64::from(*units)).ok_or_else(overflow)?,
+                ..distance
+            },
+            (MovementModel::Aim, Down(units)) => Distance {
+                aim: aim.checked_add(i64::from(*units)).ok_or_else(overflow)?,
+                ..distance
+            },
+            (MovementModel::Aim, Up(units)) => Distance {
+                aim: aim.checked_sub(i64::from(*units)).ok_or_else(overflow)?,
+                ..distance
+            },
+        };
+        if distance.depth < 0 {
+            return Err(NavigationError::AboveSurface { step });
+        }
+        Ok(distance)
+    }
+}
+
+/// A command that would take the submarine somewhere it can't go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavigationError {
+    /// The command would lift the submarine out of the water.
+    AboveSurface { step: usize },
+    /// The position would no longer fit into the counters.
+    Overflow { step: usize },
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NavigationError::AboveSurface { step } => {
+                write!(
+                    f,
+                    "step {}: the submarine would rise above the surface",
+                    step
+                )
+            }
+            NavigationError::Overflow { step } => {
+                write!(f, "step {}: the submarine would travel too far", step)
+            }
+        }
+    }
+}
+
+impl Error for NavigationError {}
+
+/// A submarine that executes one command at a time.
+#[derive(Debug, Clone)]
+pub struct Submarine {
+    model: MovementModel,
+    distance: Distance,
+    n_steps: usize,
+}
+
+impl Submarine {
+    pub fn new(model: MovementModel) -> Self {
+        Self {
+            model,
+            distance: Distance::default(),
+            n_steps: 0,
+        }
+    }
+
+    /// Leaves the submarine where it is if the command fails.
+    pub fn execute(&mut self, command: &Command) -> Result<(), NavigationError> {
+        self.distance = self.model.apply(self.n_steps + 1, self.distance, command)?;
+        self.n_steps += 1;
+        Ok(())
+    }
+
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+}
+
+pub fn calculate_distance(
+    input: &[Statement],
+    model: MovementModel,
+) -> Result<Distance, NavigationError> {
+    commands(input)
+        .enumerate()
+        .try_fold(Distance::default(), |acc, (i, command)| {
+            model.apply(i + 1, acc, command)
+        })
+}
+
+/// Where the submarine is after a command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceStep<'a> {
+    /// Starts at 1.
+    pub step: usize,
+    #[serde(serialize_with = "display")]
+    pub command: &'a Command,
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+fn display<S: Serializer>(command: &&Command, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(command)
+}
+
+/// Like calculate_distance, but yields the position after every command.
+///
+/// Ends after the first command that fails.
+pub fn trace(input: &[Statement], model: MovementModel) -> Trace<'_> {
+    Trace {
+        commands: commands(input),
+        submarine: Some(Submarine::new(model)),
+        step: 0,
+    }
+}
+
+pub struct Trace<'a> {
+    commands: Commands<'a>,
+    /// `None` after a command has failed.
+    submarine: Option<Submarine>,
+    step: usize,
+}
+
+impl<'a> Iterator for Trace<'a> {
+    type Item = Result<TraceStep<'a>, NavigationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let submarine = self.submarine.as_mut()?;
+        let command = self.commands.next()?;
+        self.step += 1;
+        if let Err(e) = submarine.execute(command) {
+            self.submarine = None;
+            return Some(Err(e));
+        }
+        let Distance {
+            horizontal,
+            depth,
+            aim,
+        } = submarine.distance();
+        Some(Ok(TraceStep {
+            step: self.step,
+            command,
+            horizontal,
+            depth,
+            aim,
+        }))
+    }
+}
+
+/// Writes the trace as CSV, with one row per command.
+///
+/// If a command fails, the rows up to that command are written before the error is returned.
+pub fn write_trace_csv(
+    mut out: impl Write,
+    input: &[Statement],
+    model: MovementModel,
+) -> anyhow::Result<()> {
+    writeln!(out, "step,command,horizontal,depth,aim")?;
+    for step in trace(input, model) {
+        let step = step?;
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            step.step, step.command, step.horizontal, step.depth, step.aim
+        )?;
+    }
+    Ok(())
+}
+
+/// Like write_trace_csv, but writes a JSON object per line.
+pub fn write_trace_json_lines(
+    mut out: impl Write,
+    input: &[Statement],
+    model: MovementModel,
+) -> anyhow::Result<()> {
+    for step in trace(input, model) {
+        serde_json::to_writer(&mut out, &step?)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+static INPUT: &str = "\
+      forward 5
+      down 5
+      forward 8
+      up 3
+      down 8
+      forward 2
+    ";
+
+#[test]
+fn test_calculating_distance_directly() {
+    let input = parse(INPUT).unwrap();
+    let Distance {
+        horizontal, depth, ..
+    } = calculate_distance(&input, MovementModel::Direct).unwrap();
+    assert_eq!(horizontal, 15);
+    assert_eq!(depth, 10);
+}
+
+#[test]
+fn test_calculating_distance() {
+    let input = parse(INPUT).unwrap();
+    let Distance {
+        horizontal, depth, ..
+    } = calculate_distance(&input, MovementModel::Aim).unwrap();
+    assert_eq!(horizontal, 15);
+    assert_eq!(depth, 60);
+}
+
+#[test]
+fn test_unknown_command() {
+    let err = parse("forward 5\n  sideways 3\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    assert_eq!(err.text, "sideways");
+}
+
+#[test]
+fn test_submarine_matches_calculate_distance() {
+    let input = parse(INPUT).unwrap();
+    for model in [MovementModel::Direct, MovementModel::Aim] {
+        let mut submarine = Submarine::new(model);
+        for command in commands(&input) {
+            submarine.execute(command).unwrap();
+        }
+        assert_eq!(
+            submarine.distance(),
+            calculate_distance(&input, model).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_rising_above_surface() {
+    let input = parse("down 2\nforward 1\nup 3\n").unwrap();
+    assert_eq!(
+        calculate_distance(&input, MovementModel::Direct),
+        Err(NavigationError::AboveSurface { step: 3 })
+    );
+    let input = parse("forward 4\nup 1\nforward 1\n").unwrap();
+    assert_eq!(
+        calculate_distance(&input, MovementModel::Aim),
+        Err(NavigationError::AboveSurface { step: 3 })
+    );
+    // Aiming upwards is fine, as long as the submarine stays in the water:
+    let input = parse("down 2\nforward 1\nup 3\nforward 1\n").unwrap();
+    let distance = calculate_distance(&input, MovementModel::Aim).unwrap();
+    assert_eq!((distance.depth, distance.aim), (1, -1));
+}
+
+#[test]
+fn test_overflow() {
+    let input = parse("down 4294967295\nforward 4294967295\n").unwrap();
+    assert_eq!(
+        calculate_distance(&input, MovementModel::Aim),
+        Err(NavigationError::Overflow { step: 2 })
+    );
+}
+
+#[test]
+fn test_extended_commands() {
+    let input = "\
+        # Leave the harbour:
+        forward 10
+        repeat 3 {
+            down 2  # dive
+            repeat 2 {
+                forward 1
+            }
+            repeat 0 {
+                forward 100
+            }
+        }
+        backward 4
+        surface
+        down 1
+    ";
+    let input = parse(input).unwrap();
+    assert_eq!(commands(&input).count(), 13);
+    let distance = calculate_distance(&input, MovementModel::Direct).unwrap();
+    assert_eq!((distance.horizontal, distance.depth), (12, 1));
+    let distance = calculate_distance(&input, MovementModel::Aim).unwrap();
+    // Diving 2 + 2 + 4 + 4 + 6 + 6 before surfacing:
+    assert_eq!(
+        (distance.horizontal, distance.depth, distance.aim),
+        (12, 0, 7)
+    );
+}
+
+#[test]
+fn test_unbalanced_blocks() {
+    let err = parse("repeat 2 {\n  forward 1\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 1));
+    assert_eq!(err.message, "block is never closed");
+    let err = parse("forward 1\n  }\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    assert_eq!(err.message, "no block to close");
+    let err = parse("repeat x {\n}\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 8));
+}
+
+#[test]
+fn test_courses_expanding_to_too_many_commands() {
+    let input = "\
+forward 1
+repeat 4000000000 {
+  repeat 4000000000 {
+    forward 0
+  }
+}
+";
+    let err = parse(input).unwrap_err();
+    assert_eq!((err.line, err.column), (3, 3));
+    assert_eq!(
+        err.message,
+        "the course expands to more than 100000000 commands"
+    );
+    // The commands before and after a block count as well:
+    let err = parse("forward 1\nrepeat 100000000 {\n  forward 1\n}\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    // Exactly at the limit is fine:
+    assert!(parse("repeat 10000 {\n  repeat 10000 {\n    forward 1\n  }\n}\n").is_ok());
+}
+
+#[test]
+fn test_trace_csv() {
+    let input = parse(INPUT).unwrap();
+    let mut csv = Vec::new();
+    write_trace_csv(&mut csv, &input, MovementModel::Aim).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "\
+step,command,horizontal,depth,aim
+1,forward 5,5,0,0
+2,down 5,5,0,5
+3,forward 8,13,40,5
+4,up 3,13,40,2
+5,down 8,13,40,10
+6,forward 2,15,60,10
+"
+    );
+}
+
+#[test]
+fn test_trace_json_lines_up_to_failure() {
+    let input = parse("down 1\nup 2\nforward 3\n").unwrap();
+    let mut json = Vec::new();
+    let err = write_trace_json_lines(&mut json, &input, MovementModel::Direct).unwrap_err();
+    assert_eq!(
+        err.downcast::<NavigationError>().unwrap(),
+        NavigationError::AboveSurface { step: 2 }
+    );
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "{\"step\":1,\"command\":\"down 1\",\"horizontal\":0,\"depth\":1,\"aim\":0}\n"
+    );
+}
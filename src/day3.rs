@@ -0,0 +1,747 @@
+use std::{cmp::Ordering, error::Error, fmt, str::FromStr};
+
+use num_bigint::BigUint;
+use serde::Serialize;
+
+use crate::{parse::numbered_lines, ParseError, Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Input;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<BigUint> {
+        let Rates { gamma, epsilon, .. } = gamma_and_epsilon_rates(input, TieBreak::PreferZero);
+        Ok(power_consumption(&gamma, &epsilon).0)
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<BigUint> {
+        let oxygen_rating = oxygen_generator_rating(input, TieBreak::PreferOne)?;
+        let co2_scrubber_rating = co2_scrubber_rating(input, TieBreak::PreferZero)?;
+        Ok(life_support_rating(&oxygen_rating, &co2_scrubber_rating).0)
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    n_diagnostic_bits: usize,
+    values: Vec<Word>,
+}
+
+impl FromStr for Input {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<(usize, &str)> = numbered_lines(s).collect();
+        let (first_line_no, first_line) = match lines.first() {
+            Some(&line) => line,
+            None => return Err(ParseError::new(1, "", "", "expected diagnostic values")),
+        };
+        let n_diagnostic_bits = first_line.trim().len();
+        let values = lines
+            .iter()
+            .map(|&(line_no, line)| {
+                let word = line.trim();
+                if let Some((pos, c)) = word.char_indices().find(|&(_, c)| c != '0' && c != '1') {
+                    let c = &word[pos..pos + c.len_utf8()];
+                    return Err(ParseError::new(line_no, line, c, "expected `0` or `1`"));
+                }
+                if word.len() != n_diagnostic_bits {
+                    return Err(ParseError::new(
+                        line_no,
+                        line,
+                        word,
+                        format!(
+                            "expected {} bits, like on line {}",
+                            n_diagnostic_bits, first_line_no
+                        ),
+                    ));
+                }
+                Ok(Word::from_bits(word.bytes().map(|b| b == b'1')))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Self {
+            n_diagnostic_bits,
+            values,
+        })
+    }
+}
+
+/// A diagnostic value of any width.
+///
+/// Bits are addressed by their position from the left, so position 0 is the most
+/// significant bit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Word {
+    width: usize,
+    /// The value, least significant digit first.
+    digits: Vec<u32>,
+}
+
+impl Word {
+    pub fn zero(width: usize) -> Self {
+        Self {
+            width,
+            digits: vec![0; width.div_ceil(32)],
+        }
+    }
+
+    /// Creates a word from its bits, the most significant one first.
+    pub fn from_bits(bits: impl ExactSizeIterator<Item = bool>) -> Self {
+        let mut word = Self::zero(bits.len());
+        for (pos, bit) in bits.enumerate() {
+            if bit {
+                word.set(pos);
+            }
+        }
+        word
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn bit(&self, pos: usize) -> bool {
+        let (digit, mask) = self.locate(pos);
+        self.digits[digit] & mask != 0
+    }
+
+    pub fn set(&mut self, pos: usize) {
+        let (digit, mask) = self.locate(pos);
+        self.digits[digit] |= mask;
+    }
+
+    /// Flips all bits.
+    pub fn inverted(&self) -> Self {
+        Self::from_bits((0..self.width).map(|pos| !self.bit(pos)))
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::new(self.digits.clone())
+    }
+
+    fn locate(&self, pos: usize) -> (usize, u32) {
+        assert!(pos < self.width, "bit {} of a {}-bit word", pos, self.width);
+        let shift = self.width - 1 - pos;
+        (shift / 32, 1 << (shift % 32))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GammaRate(pub BigUint);
+#[derive(Debug, Clone)]
+pub struct EpsilonRate(pub BigUint);
+#[derive(Debug, Clone)]
+pub struct PowerConsumption(pub BigUint);
+#[derive(Debug, Clone)]
+pub struct OxygenGeneratorRating(pub BigUint);
+#[derive(Debug, Clone)]
+pub struct Co2ScrubberRating(pub BigUint);
+#[derive(Debug, Clone)]
+pub struct LifeSupportRating(pub BigUint);
+
+/// Which bit wins if 0 and 1 are equally common.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    PreferOne,
+    PreferZero,
+}
+
+impl TieBreak {
+    fn winner(self) -> bool {
+        self == TieBreak::PreferOne
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rates {
+    pub gamma: GammaRate,
+    pub epsilon: EpsilonRate,
+    /// The bit positions where 0 and 1 were equally common.
+    pub ties: Vec<usize>,
+}
+
+/// The puzzle doesn't say what to do about ties; part 1 uses `TieBreak::PreferZero`.
+pub fn gamma_and_epsilon_rates(
+    Input {
+        n_diagnostic_bits,
+        values,
+    }: &Input,
+    tie_break: TieBreak,
+) -> Rates {
+    let mut gamma_rate = Word::zero(*n_diagnostic_bits);
+    let mut ties = Vec::new();
+    // We scan the individual bits one after the other, starting with the first, most-left bit.
+    for pos in 0..*n_diagnostic_bits {
+        // For each input number, select the nth bit and count the non-zero numbers:
+        let n_one_bits = values.iter().filter(|value| value.bit(pos)).count();
+        let n_zero_bits = values.len() - n_one_bits;
+        let most_common_bit = match n_one_bits.cmp(&n_zero_bits) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => {
+                ties.push(pos);
+                tie_break.winner()
+            }
+        };
+        if most_common_bit {
+            // Set that bit on the gamma rate:
+            gamma_rate.set(pos);
+        }
+    }
+    // Epsilon is gamma, flipped:
+    let epsilon_rate = gamma_rate.inverted();
+    Rates {
+        gamma: GammaRate(gamma_rate.to_biguint()),
+        epsilon: EpsilonRate(epsilon_rate.to_biguint()),
+        ties,
+    }
+}
+
+pub fn power_consumption(gamma_rate: &GammaRate, epsilon_rate: &EpsilonRate) -> PowerConsumption {
+    PowerConsumption(&gamma_rate.0 * &epsilon_rate.0)
+}
+
+/// Which values are retained at each bit position when looking for a rating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitCriteria {
+    /// Used for the oxygen generator rating.
+    MostCommon,
+    /// Used for the CO2 scrubber rating.
+    LeastCommon,
+}
+
+/// The values that remain after filtering them bit by bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filtering {
+    /// More than one if some values are equal; never empty.
+    pub candidates: Vec<Word>,
+    /// The bit positions where 0 and 1 were equally common.
+    pub ties: Vec<usize>,
+    /// How many values were left after each bit position, until only one was left.
+    pub n_remaining: Vec<usize>,
+}
+
+/// Filters the values bit by bit, starting with the most-left bit, until only one is left.
+///
+/// Values that don't match the criteria at a bit position are discarded, unless that would
+/// discard all of them. If 0 and 1 are equally common, `tie_break` decides which is kept.
+pub fn filter_by_bit_criteria(
+    Input {
+        n_diagnostic_bits,
+        values,
+    }: &Input,
+    criteria: BitCriteria,
+    tie_break: TieBreak,
+) -> Filtering {
+    let mut values = values.clone();
+    let mut ties = Vec::new();
+    let mut n_remaining = Vec::new();
+    for pos in 0..*n_diagnostic_bits {
+        if values.len() <= 1 {
+            break;
+        }
+        let (values_with_low_bit, values_with_high_bit) = split_by_bit(values, pos);
+        let keep_high_bit = match (values_with_low_bit.len(), values_with_high_bit.len()) {
+            (0, _) => true,
+            (_, 0) => false,
+            (n_low, n_high) if n_low == n_high => {
+                ties.push(pos);
+                tie_break.winner()
+            }
+            (n_low, n_high) => (n_high > n_low) == (criteria == BitCriteria::MostCommon),
+        };
+        values = if keep_high_bit {
+            values_with_high_bit
+        } else {
+            values_with_low_bit
+        };
+        n_remaining.push(values.len());
+    }
+    Filtering {
+        candidates: values,
+        ties,
+        n_remaining,
+    }
+}
+
+fn split_by_bit(values: Vec<Word>, pos: usize) -> (Vec<Word>, Vec<Word>) {
+    let mut acc = (vec![], vec![]);
+    for value in values {
+        match value.bit(pos) {
+            false => acc.0.push(value),
+            true => acc.1.push(value),
+        };
+    }
+    acc
+}
+
+/// More than one value is left after filtering, so there is no single rating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguousRating(pub Filtering);
+
+impl fmt::Display for AmbiguousRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} candidates remain:", self.0.candidates.len())?;
+        for candidate in &self.0.candidates {
+            write!(f, " {}", candidate.to_biguint())?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for AmbiguousRating {}
+
+fn only_candidate(filtering: Filtering) -> Result<BigUint, AmbiguousRating> {
+    match &filtering.candidates[..] {
+        [value] => Ok(value.to_biguint()),
+        _ => Err(AmbiguousRating(filtering)),
+    }
+}
+
+/// Part 2 keeps values with a 1 if 0 and 1 are equally common, so `TieBreak::PreferOne`.
+pub fn oxygen_generator_rating(
+    input: &Input,
+    tie_break: TieBreak,
+) -> Result<OxygenGeneratorRating, AmbiguousRating> {
+    let filtering = filter_by_bit_criteria(input, BitCriteria::MostCommon, tie_break);
+    only_candidate(filtering).map(OxygenGeneratorRating)
+}
+
+/// Part 2 keeps values with a 0 if 0 and 1 are equally common, so `TieBreak::PreferZero`.
+pub fn co2_scrubber_rating(
+    input: &Input,
+    tie_break: TieBreak,
+) -> Result<Co2ScrubberRating, AmbiguousRating> {
+    let filtering = filter_by_bit_criteria(input, BitCriteria::LeastCommon, tie_break);
+    only_candidate(filtering).map(Co2ScrubberRating)
+}
+
+/// The values as a binary trie, in which every node counts the values sharing its prefix.
+///
+/// Unlike filter_by_bit_criteria, which splits the remaining values at every bit, finding
+/// a rating is a single walk from the root to a leaf.
+#[derive(Debug, Clone)]
+pub struct BitTrie {
+    n_diagnostic_bits: usize,
+    /// The root is the first node.
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    n_values: usize,
+    /// Indexed by the next bit.
+    children: [Option<usize>; 2],
+}
+
+impl BitTrie {
+    pub fn new(input: &Input) -> Self {
+        let mut nodes = vec![TrieNode::default()];
+        for value in &input.values {
+            let mut node = 0;
+            nodes[node].n_values += 1;
+            for pos in 0..input.n_diagnostic_bits {
+                let bit = value.bit(pos) as usize;
+                node = match nodes[node].children[bit] {
+                    Some(child) => child,
+                    None => {
+                        nodes.push(TrieNode::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children[bit] = Some(child);
+                        child
+                    }
+                };
+                nodes[node].n_values += 1;
+            }
+        }
+        Self {
+            n_diagnostic_bits: input.n_diagnostic_bits,
+            nodes,
+        }
+    }
+
+    /// Finds the same values as filter_by_bit_criteria.
+    pub fn filter(&self, criteria: BitCriteria, tie_break: TieBreak) -> Filtering {
+        let mut word = Word::zero(self.n_diagnostic_bits);
+        let mut ties = Vec::new();
+        let mut n_remaining = Vec::new();
+        let mut node = &self.nodes[0];
+        for pos in 0..self.n_diagnostic_bits {
+            let n_values = |bit: usize| node.children[bit].map_or(0, |i| self.nodes[i].n_values);
+            let keep_high_bit = match (n_values(0), n_values(1)) {
+                (0, _) => true,
+                (_, 0) => false,
+                (n_low, n_high) if n_low == n_high => {
+                    ties.push(pos);
+                    tie_break.winner()
+                }
+                (n_low, n_high) => (n_high > n_low) == (criteria == BitCriteria::MostCommon),
+            };
+            // Once only one value is left, we just follow its path:
+            if node.n_values > 1 {
+                n_remaining.push(n_values(keep_high_bit as usize));
+            }
+            if keep_high_bit {
+                word.set(pos);
+            }
+            match node.children[keep_high_bit as usize] {
+                Some(child) => node = &self.nodes[child],
+                // There are no values at all:
+                None => break,
+            }
+        }
+        Filtering {
+            candidates: vec![word; node.n_values],
+            ties,
+            n_remaining,
+        }
+    }
+}
+
+/// Statistics of the bits at one position, from the left.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnStats {
+    pub pos: usize,
+    pub n_ones: usize,
+    pub n_zeros: usize,
+    /// The share of ones, between 0 and 1.
+    pub ratio: f64,
+    /// The Shannon entropy in bits: 0 if all bits are equal, 1 if 0 and 1 are equally common.
+    pub entropy: f64,
+    /// How many values were left for the oxygen generator rating after this position.
+    pub oxygen_candidates: Option<usize>,
+    /// How many values were left for the CO2 scrubber rating after this position.
+    pub co2_candidates: Option<usize>,
+}
+
+/// Describes every column, following the ratings of part 2 as they are filtered.
+pub fn column_stats(input: &Input) -> Vec<ColumnStats> {
+    let oxygen = filter_by_bit_criteria(input, BitCriteria::MostCommon, TieBreak::PreferOne);
+    let co2 = filter_by_bit_criteria(input, BitCriteria::LeastCommon, TieBreak::PreferZero);
+    (0..input.n_diagnostic_bits)
+        .map(|pos| {
+            let n_ones = input.values.iter().filter(|value| value.bit(pos)).count();
+            let n_zeros = input.values.len() - n_ones;
+            let ratio = n_ones as f64 / input.values.len() as f64;
+            let entropy = [ratio, 1. - ratio]
+                .iter()
+                .filter(|&&p| p > 0.)
+                .map(|&p| -p * p.log2())
+                .sum();
+            ColumnStats {
+                pos,
+                n_ones,
+                n_zeros,
+                ratio,
+                entropy,
+                oxygen_candidates: oxygen.n_remaining.get(pos).copied(),
+                co2_candidates: co2.n_remaining.get(pos).copied(),
+            }
+        })
+        .collect()
+}
+
+/// Formats the column stats as a table, with a row per column.
+pub fn column_stats_table(columns: &[ColumnStats]) -> String {
+    let fmt = |n: Option<usize>| n.map_or_else(|| "-".to_owned(), |n| n.to_string());
+    let mut table = format!(
+        "{:>4} {:>8} {:>8} {:>6} {:>7} {:>7} {:>7}\n",
+        "bit", "ones", "zeros", "ratio", "entropy", "oxygen", "co2"
+    );
+    for column in columns {
+        table += &format!(
+            "{:>4} {:>8} {:>8} {:>6.3} {:>7.3} {:>7} {:>7}\n",
+            column.pos,
+            column.n_ones,
+            column.n_zeros,
+            column.ratio,
+            column.entropy,
+            fmt(column.oxygen_candidates),
+            fmt(column.co2_candidates)
+        );
+    }
+    table
+}
+
+pub fn life_support_rating(
+    oxygen_generator_rating: &OxygenGeneratorRating,
+    co2_scrubber_rating: &Co2ScrubberRating,
+) -> LifeSupportRating {
+    LifeSupportRating(&oxygen_generator_rating.0 * &co2_scrubber_rating.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_computing_power_consumption() {
+        let input = "\
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+            ";
+        let input = input.parse().unwrap();
+        let Rates {
+            gamma: gamma_rate,
+            epsilon: epsilon_rate,
+            ..
+        } = gamma_and_epsilon_rates(&input, TieBreak::PreferZero);
+        let power_consumption = power_consumption(&gamma_rate, &epsilon_rate);
+        assert_eq!(gamma_rate.0, 22u32.into());
+        assert_eq!(epsilon_rate.0, 9u32.into());
+        assert_eq!(power_consumption.0, 198u32.into());
+    }
+
+    #[test]
+    fn test_oxygen_rating_uses_most_common_bits() {
+        // For 2nd column, 0 is the most common bit initially, but after processing the 1st
+        // column, the first two lines are discarded; after that, the most common value in
+        // the 2nd column is 1.
+        let input = "\
+            000
+            001
+            111
+            110
+            101
+            ";
+        let input = input.parse().unwrap();
+        let gamma_rate = gamma_and_epsilon_rates(&input, TieBreak::PreferZero).gamma;
+        // Using the gamma_rate, we'd pick the wrong numbers:
+        assert_eq!(gamma_rate.0, 0b101u32.into());
+        let oxygen_rating = oxygen_generator_rating(&input, TieBreak::PreferOne).unwrap();
+        assert_eq!(oxygen_rating.0, 0b111u32.into());
+    }
+
+    #[test]
+    fn test_oxygen_rating_prefers_high_bits() {
+        let input = "\
+            000
+            111
+            ";
+        let input = input.parse().unwrap();
+        let oxygen_rating = oxygen_generator_rating(&input, TieBreak::PreferOne).unwrap();
+        assert_eq!(oxygen_rating.0, 0b111u32.into());
+    }
+
+    #[test]
+    fn test_co2_scrubber_rating_uses_least_common_bits() {
+        // For 2nd column, 0 is the most common bit initially, but after processing the 1st
+        // column, the first two lines are discarded; after that, the most common value in
+        // the 2nd column is 1.
+        let input = "\
+            000
+            001
+            111
+            110
+            101
+            ";
+        let input = input.parse().unwrap();
+        let epsilon_rate = gamma_and_epsilon_rates(&input, TieBreak::PreferZero).epsilon;
+        // Using the epsilon_rate, we'd pick the wrong numbers:
+        assert_eq!(epsilon_rate.0, 0b010u32.into());
+        let co2_scrubber_rating = co2_scrubber_rating(&input, TieBreak::PreferZero).unwrap();
+        assert_eq!(co2_scrubber_rating.0, 0b000u32.into());
+    }
+
+    #[test]
+    fn test_co2_scrubber_rating_prefers_low_bits() {
+        let input = "\
+            000
+            111
+            ";
+        let input = input.parse().unwrap();
+        let oxygen_rating = co2_scrubber_rating(&input, TieBreak::PreferZero).unwrap();
+        assert_eq!(oxygen_rating.0, 0b000u32.into());
+    }
+
+    #[test]
+    fn test_life_support_rating() {
+        let input = "\
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+            ";
+        let input = input.parse().unwrap();
+        let oxygen_rating = oxygen_generator_rating(&input, TieBreak::PreferOne).unwrap();
+        assert_eq!(oxygen_rating.0, 23u32.into());
+        let co2_scrubber_rating = co2_scrubber_rating(&input, TieBreak::PreferZero).unwrap();
+        assert_eq!(co2_scrubber_rating.0, 10u32.into());
+        let life_support_rating = life_support_rating(&oxygen_rating, &co2_scrubber_rating);
+        assert_eq!(life_support_rating.0, 230u32.into());
+    }
+
+    #[test]
+    fn test_parse_error_for_different_width() {
+        let err = "00100\n1110\n".parse::<Input>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.text, "1110");
+    }
+
+    #[test]
+    fn test_wide_words() {
+        // The example, with 95 ones put in front of every word:
+        let input = "\
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+            ";
+        let prefix = "1".repeat(95);
+        let input: String = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| format!("{}{}\n", prefix, line.trim()))
+            .collect();
+        let input: Input = input.parse().unwrap();
+        let high_bits = BigUint::from(1u32) << 100u32;
+        let Rates {
+            gamma: gamma_rate,
+            epsilon: epsilon_rate,
+            ..
+        } = gamma_and_epsilon_rates(&input, TieBreak::PreferZero);
+        assert_eq!(gamma_rate.0, high_bits.clone() - (1u32 << 5) + 22u32);
+        assert_eq!(epsilon_rate.0, 9u32.into());
+        assert_eq!(
+            oxygen_generator_rating(&input, TieBreak::PreferOne)
+                .unwrap()
+                .0,
+            high_bits.clone() - (1u32 << 5) + 23u32
+        );
+        assert_eq!(
+            co2_scrubber_rating(&input, TieBreak::PreferZero).unwrap().0,
+            high_bits - (1u32 << 5) + 10u32
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        let input = "\
+            0110
+            0101
+            1011
+            1000
+            ";
+        let input = input.parse().unwrap();
+        let rates = gamma_and_epsilon_rates(&input, TieBreak::PreferOne);
+        assert_eq!(rates.ties, vec![0, 1, 2, 3]);
+        assert_eq!(rates.gamma.0, 0b1111u32.into());
+        let rates = gamma_and_epsilon_rates(&input, TieBreak::PreferZero);
+        assert_eq!(rates.gamma.0, 0b0000u32.into());
+
+        let filtering =
+            filter_by_bit_criteria(&input, BitCriteria::MostCommon, TieBreak::PreferZero);
+        assert_eq!(filtering.ties, vec![0, 2]);
+        assert_eq!(filtering.candidates.len(), 1);
+        assert_eq!(filtering.candidates[0].to_biguint(), 0b0101u32.into());
+    }
+
+    #[test]
+    fn test_ambiguous_rating_reports_candidates() {
+        let input = "\
+            101
+            101
+            000
+            ";
+        let input = input.parse().unwrap();
+        let err = oxygen_generator_rating(&input, TieBreak::PreferOne).unwrap_err();
+        let AmbiguousRating(Filtering {
+            candidates, ties, ..
+        }) = &err;
+        assert_eq!(candidates.len(), 2);
+        assert!(ties.is_empty());
+        assert_eq!(err.to_string(), "2 candidates remain: 5 5");
+        // The CO2 scrubber rating is still unique:
+        let co2_scrubber_rating = co2_scrubber_rating(&input, TieBreak::PreferZero).unwrap();
+        assert_eq!(co2_scrubber_rating.0, 0u32.into());
+    }
+
+    #[test]
+    fn test_column_stats() {
+        let input = "\
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+            ";
+        let input = input.parse().unwrap();
+        let columns = column_stats(&input);
+        let counts: Vec<_> = columns.iter().map(|c| (c.n_ones, c.n_zeros)).collect();
+        assert_eq!(counts, vec![(7, 5), (5, 7), (8, 4), (7, 5), (5, 7)]);
+        assert!((columns[2].ratio - 2. / 3.).abs() < 1e-9);
+        assert!((columns[2].entropy - 0.918).abs() < 1e-3);
+        // As in the puzzle description:
+        let oxygen: Vec<_> = columns.iter().map(|c| c.oxygen_candidates).collect();
+        assert_eq!(oxygen, vec![Some(7), Some(4), Some(3), Some(2), Some(1)]);
+        let co2: Vec<_> = columns.iter().map(|c| c.co2_candidates).collect();
+        assert_eq!(co2, vec![Some(5), Some(2), Some(1), None, None]);
+        assert_eq!(
+            column_stats_table(&columns).lines().nth(4).unwrap(),
+            "   3        7        5  0.583   0.980       2       -"
+        );
+    }
+
+    #[test]
+    fn test_trie_matches_filtering() {
+        let inputs = [
+            "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n",
+            "000\n001\n111\n110\n101\n",
+            "0110\n0101\n1011\n1000\n",
+            "101\n101\n000\n",
+            "1\n",
+        ];
+        for input in inputs {
+            let input: Input = input.parse().unwrap();
+            let trie = BitTrie::new(&input);
+            for criteria in [BitCriteria::MostCommon, BitCriteria::LeastCommon] {
+                for tie_break in [TieBreak::PreferOne, TieBreak::PreferZero] {
+                    assert_eq!(
+                        trie.filter(criteria, tie_break),
+                        filter_by_bit_criteria(&input, criteria, tie_break)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,717 @@
+use std::{collections::HashMap, fmt, vec};
+
+use anyhow::bail;
+
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = (Vec<Board>, DrawnNumbers);
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((boards, drawn_numbers): &Self::Input) -> anyhow::Result<u128> {
+        match play_to_win(boards.clone(), drawn_numbers.clone(), STANDARD) {
+            BingoResult::Bingo { score, .. } => Ok(score),
+            BingoResult::NoBingo => bail!("no board has BINGO"),
+        }
+    }
+
+    fn part2((boards, drawn_numbers): &Self::Input) -> anyhow::Result<u128> {
+        match play_to_lose(boards.clone(), drawn_numbers.clone(), STANDARD) {
+            BingoResult::Bingo { score, .. } => Ok(score),
+            BingoResult::NoBingo => bail!("no board has BINGO"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrawnNumbers(Vec<u32>);
+impl DrawnNumbers {
+    pub fn try_from(line_no: usize, line: &str) -> Result<Self, ParseError> {
+        let nums = line
+            .split(',')
+            .map(|x| parse::number(line_no, line, x.trim()))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+        Ok(Self(nums))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    // n_rows x n_cols board, row by row.
+    fields: Vec<Vec<u32>>,
+    // To quickly check whether a number is on the board; maps number to (row,col).
+    field_set: HashMap<u32, (usize, usize)>,
+    // To mark drawn numbers.
+    marks: Vec<Vec<bool>>,
+    // Only set if BINGO.
+    score: Option<u128>,
+}
+impl Board {
+    /// Expects the rows of the board, given with their line numbers.
+    ///
+    /// The board has as many columns as there are numbers in the first row.
+    pub fn try_from(lines: &[(usize, &str)]) -> Result<Self, ParseError> {
+        let mut fields: Vec<Vec<u32>> = Vec::with_capacity(lines.len());
+        let mut field_set: HashMap<u32, (usize, usize)> = HashMap::new();
+        for (row, &(line_no, line)) in lines.iter().enumerate() {
+            let texts: Vec<&str> = line.split_whitespace().collect();
+            if let Some(first_row) = fields.first() {
+                let n_cols = first_row.len();
+                if texts.len() != n_cols {
+                    // Point at the first number too many, or at the whole row if it's too short:
+                    let text = texts.get(n_cols).copied().unwrap_or(line.trim());
+                    return Err(ParseError::new(
+                        line_no,
+                        line,
+                        text,
+                        format!(
+                            "expected {} numbers per row, like on line {}",
+                            n_cols, lines[0].0
+                        ),
+                    ));
+                }
+            }
+            let mut nums = Vec::with_capacity(texts.len());
+            for (col, text) in texts.into_iter().enumerate() {
+                let num = parse::number(line_no, line, text)?;
+                // Marking a number marks a single field, so every number must be unique:
+                if let Some(&(other_row, _)) = field_set.get(&num) {
+                    return Err(ParseError::new(
+                        line_no,
+                        line,
+                        text,
+                        format!("duplicate number, already on line {}", lines[other_row].0),
+                    ));
+                }
+                field_set.insert(num, (row, col));
+                nums.push(num);
+            }
+            fields.push(nums);
+        }
+        let marks = fields.iter().map(|row| vec![false; row.len()]).collect();
+        Ok(Board {
+            fields,
+            field_set,
+            marks,
+            score: None,
+        })
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.fields.first().map_or(0, Vec::len)
+    }
+
+    /// `lines` are the groups of fields that make BINGO on a board of this size, as returned
+    /// by win_lines.
+    pub fn mark(&mut self, num: u32, lines: &[Cells], scoring: Scoring) -> BoardResult {
+        if let Some((row, col)) = self.field_set.get(&num) {
+            let row = *row;
+            let col = *col;
+
+            self.marks[row][col] = true;
+
+            if let Some(line) = self.completed_line(lines) {
+                let score = scoring.score(self, line, num);
+                self.score = Some(score);
+                BoardResult::Bingo {
+                    score,
+                    line: line.clone(),
+                }
+            } else {
+                BoardResult::NoBingo
+            }
+        } else {
+            BoardResult::NoBingo
+        }
+    }
+
+    pub fn contains(&self, num: u32) -> bool {
+        self.field_set.contains_key(&num)
+    }
+
+    /// Whether the board has BINGO already.
+    pub fn has_won(&self) -> bool {
+        self.score.is_some()
+    }
+
+    fn completed_line<'l>(&self, lines: &'l [Cells]) -> Option<&'l Cells> {
+        lines
+            .iter()
+            .find(|line| line.iter().all(|&(row, col)| self.marks[row][col]))
+    }
+}
+
+/// How the score of a board with BINGO is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scoring {
+    /// The rules of the puzzle.
+    #[default]
+    UnmarkedSumTimesLastNumber,
+    UnmarkedSum,
+    /// The sum of the fields that made BINGO, multiplied by the last number.
+    LineSumTimesLastNumber,
+}
+
+impl Scoring {
+    /// Computed in u128, which no sum of u32 fields times a u32 number can overflow.
+    fn score(self, board: &Board, line: &Cells, last_num: u32) -> u128 {
+        let sum_unmarked = || {
+            let mut sum_unmarked = 0;
+            for row in 0..board.n_rows() {
+                for col in 0..board.n_cols() {
+                    if !board.marks[row][col] {
+                        sum_unmarked += u128::from(board.fields[row][col]);
+                    }
+                }
+            }
+            sum_unmarked
+        };
+        let last_num = u128::from(last_num);
+        match self {
+            Scoring::UnmarkedSumTimesLastNumber => sum_unmarked() * last_num,
+            Scoring::UnmarkedSum => sum_unmarked(),
+            Scoring::LineSumTimesLastNumber => {
+                let line_sum: u128 = line
+                    .iter()
+                    .map(|&(row, col)| u128::from(board.fields[row][col]))
+                    .sum();
+                line_sum * last_num
+            }
+        }
+    }
+}
+
+/// The fields of a board, as (row, col).
+pub type Cells = Vec<(usize, usize)>;
+
+/// Decides which fields need to be marked for BINGO.
+pub trait WinPattern {
+    /// All groups of fields that make BINGO once they're all marked, for a board of the
+    /// given size.
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells>;
+}
+
+/// The groups of fields that make BINGO under any of the patterns, for a board of the given
+/// size.
+pub fn win_lines(patterns: &[&dyn WinPattern], n_rows: usize, n_cols: usize) -> Vec<Cells> {
+    patterns
+        .iter()
+        .flat_map(|pattern| pattern.lines(n_rows, n_cols))
+        .collect()
+}
+
+/// The rules of the puzzle: any complete row or column.
+pub const STANDARD: &[&dyn WinPattern] = &[&Row, &Column];
+
+pub struct Row;
+impl WinPattern for Row {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        (0..n_rows)
+            .map(|row| (0..n_cols).map(|col| (row, col)).collect())
+            .collect()
+    }
+}
+
+pub struct Column;
+impl WinPattern for Column {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        (0..n_cols)
+            .map(|col| (0..n_rows).map(|row| (row, col)).collect())
+            .collect()
+    }
+}
+
+/// Either diagonal; only square boards have diagonals.
+pub struct Diagonals;
+impl WinPattern for Diagonals {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        if n_rows != n_cols {
+            return vec![];
+        }
+        let n = n_rows;
+        vec![
+            (0..n).map(|i| (i, i)).collect(),
+            (0..n).map(|i| (i, n - 1 - i)).collect(),
+        ]
+    }
+}
+
+pub struct FourCorners;
+impl WinPattern for FourCorners {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        if n_rows == 0 || n_cols == 0 {
+            return vec![];
+        }
+        let (last_row, last_col) = (n_rows - 1, n_cols - 1);
+        vec![vec![
+            (0, 0),
+            (0, last_col),
+            (last_row, 0),
+            (last_row, last_col),
+        ]]
+    }
+}
+
+/// Both diagonals at once.
+pub struct X;
+impl WinPattern for X {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        let diagonals = Diagonals.lines(n_rows, n_cols);
+        if diagonals.is_empty() {
+            return vec![];
+        }
+        vec![diagonals.concat()]
+    }
+}
+
+/// Every field on the board.
+pub struct Blackout;
+impl WinPattern for Blackout {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        vec![Row.lines(n_rows, n_cols).concat()]
+    }
+}
+
+/// The fields set in the mask, given row by row and anchored at the top-left field of the
+/// board; never wins on boards the mask doesn't fit, nor if no field is set.
+pub struct Mask(pub Vec<Vec<bool>>);
+impl WinPattern for Mask {
+    fn lines(&self, n_rows: usize, n_cols: usize) -> Vec<Cells> {
+        let fits = self.0.len() <= n_rows && self.0.iter().all(|row| row.len() <= n_cols);
+        if !fits {
+            return vec![];
+        }
+        let cells = self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(row, mask)| {
+                mask.iter()
+                    .enumerate()
+                    .filter(|(_, &set)| set)
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect::<Cells>();
+        if cells.is_empty() {
+            return vec![];
+        }
+        vec![cells]
+    }
+}
+
+pub enum BoardResult {
+    /// `line` is the group of fields that has been completed.
+    Bingo {
+        score: u128,
+        line: Cells,
+    },
+    NoBingo,
+}
+
+pub fn parse(s: &str) -> Result<(Vec<Board>, DrawnNumbers), ParseError> {
+    let mut lines = numbered_lines(s).peekable();
+    // first line is drawn numbers:
+    let drawn_numbers = match lines.next() {
+        Some((line_no, line)) => DrawnNumbers::try_from(line_no, line)?,
+        None => return Err(ParseError::new(1, "", "", "expected the drawn numbers")),
+    };
+    // boards are separated by empty lines:
+    let mut boards = Vec::new();
+    while let Some(&(first_line_no, _)) = lines.peek() {
+        let mut block = Vec::new();
+        while let Some(&(line_no, line)) = lines.peek() {
+            if line_no != first_line_no + block.len() {
+                break;
+            }
+            block.push((line_no, line));
+            lines.next();
+        }
+        let board = Board::try_from(&block).map_err(|mut e| {
+            e.message = format!("board {}: {}", boards.len() + 1, e.message);
+            e
+        })?;
+        boards.push(board);
+    }
+    Ok((boards, drawn_numbers))
+}
+
+/// Plays bingo one drawn number at a time, yielding what happened for every number.
+///
+/// Boards stop playing once they have BINGO.
+pub struct BingoGame {
+    boards: Vec<Board>,
+    drawn_numbers: vec::IntoIter<u32>,
+    // The win lines for each board size, as (n_rows, n_cols); computed only once.
+    lines: HashMap<(usize, usize), Vec<Cells>>,
+    scoring: Scoring,
+    n_drawn: usize,
+}
+
+impl BingoGame {
+    pub fn new(
+        boards: Vec<Board>,
+        drawn_numbers: DrawnNumbers,
+        patterns: &[&dyn WinPattern],
+    ) -> Self {
+        let mut lines = HashMap::new();
+        for board in &boards {
+            let size = (board.n_rows(), board.n_cols());
+            lines
+                .entry(size)
+                .or_insert_with(|| win_lines(patterns, size.0, size.1));
+        }
+        Self {
+            boards,
+            drawn_numbers: drawn_numbers.0.into_iter(),
+            lines,
+            scoring: Scoring::default(),
+            n_drawn: 0,
+        }
+    }
+
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Plays until the numbers run out and returns the boards that won, in order.
+    pub fn finishing_order(self) -> Vec<Completion> {
+        self.flat_map(|event| event.completed).collect()
+    }
+}
+
+impl Iterator for BingoGame {
+    type Item = DrawEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let num = self.drawn_numbers.next()?;
+        self.n_drawn += 1;
+        let mut event = DrawEvent {
+            draw_no: self.n_drawn,
+            num,
+            marked_by: vec![],
+            completed: vec![],
+        };
+        for (i, board) in self.boards.iter_mut().enumerate() {
+            if board.has_won() || !board.contains(num) {
+                continue;
+            }
+            event.marked_by.push(i + 1);
+            let lines = &self.lines[&(board.n_rows(), board.n_cols())];
+            if let BoardResult::Bingo { score, line } = board.mark(num, lines, self.scoring) {
+                event.completed.push(Completion {
+                    board_no: i + 1,
+                    line,
+                    score,
+                });
+            }
+        }
+        Some(event)
+    }
+}
+
+/// What happened when a number was drawn. Boards are numbered starting with 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawEvent {
+    /// Starts at 1.
+    pub draw_no: usize,
+    pub num: u32,
+    /// The boards that are still playing and have the number.
+    pub marked_by: Vec<usize>,
+    pub completed: Vec<Completion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub board_no: usize,
+    pub line: Cells,
+    pub score: u128,
+}
+
+impl fmt::Display for DrawEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "draw {}: {}", self.draw_no, self.num)?;
+        if !self.marked_by.is_empty() {
+            let boards: Vec<String> = self.marked_by.iter().map(|i| i.to_string()).collect();
+            let plural = if boards.len() > 1 { "s" } else { "" };
+            write!(f, ", marked on board{} {}", plural, boards.join(", "))?;
+        }
+        for Completion {
+            board_no,
+            line,
+            score,
+        } in &self.completed
+        {
+            let line: Vec<String> = line
+                .iter()
+                .map(|(row, col)| format!("({},{})", row, col))
+                .collect();
+            write!(
+                f,
+                "; BINGO on board {} with {}, score {}",
+                board_no,
+                line.join(" "),
+                score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn play_to_win(
+    boards: Vec<Board>,
+    drawn_numbers: DrawnNumbers,
+    patterns: &[&dyn WinPattern],
+) -> BingoResult {
+    BingoGame::new(boards, drawn_numbers, patterns)
+        .flat_map(|event| event.completed)
+        .next()
+        .map_or(
+            BingoResult::NoBingo,
+            |Completion {
+                 board_no, score, ..
+             }| { BingoResult::Bingo { board_no, score } },
+        )
+}
+
+/// Finds the board that is the last to have BINGO; only if all boards get there.
+pub fn play_to_lose(
+    boards: Vec<Board>,
+    drawn_numbers: DrawnNumbers,
+    patterns: &[&dyn WinPattern],
+) -> BingoResult {
+    let n_boards = boards.len();
+    let finishing_order = BingoGame::new(boards, drawn_numbers, patterns).finishing_order();
+    match finishing_order.last() {
+        Some(&Completion {
+            board_no, score, ..
+        }) if finishing_order.len() == n_boards => BingoResult::Bingo { board_no, score },
+        _ => BingoResult::NoBingo,
+    }
+}
+
+pub enum BingoResult {
+    Bingo { board_no: usize, score: u128 },
+    NoBingo,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "\
+        7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+        22 13 17 11  0
+        8  2 23  4 24
+        21  9 14 16  7
+        6 10  3 18  5
+        1 12 20 15 19
+        
+        3 15  0  2 22
+        9 18 13 17  5
+        19  8  7 25 23
+        20 11 10 24  4
+        14 21 16 12  6
+        
+        14 21 17 24  4
+        10 16 15  9 19
+        18  8 23 26 20
+        22 11 13  6  5
+        2  0 12  3  7
+        ";
+
+    #[test]
+    fn test_play_to_win() {
+        let (boards, drawn_numbers) = parse(INPUT).unwrap();
+        match play_to_win(boards, drawn_numbers, STANDARD) {
+            BingoResult::Bingo { board_no, score } => {
+                assert_eq!(board_no, 3);
+                assert_eq!(score, 4512);
+            }
+            _ => panic!("bingo expected"),
+        };
+    }
+
+    #[test]
+    fn test_play_to_lose() {
+        let (boards, drawn_numbers) = parse(INPUT).unwrap();
+        match play_to_lose(boards, drawn_numbers, STANDARD) {
+            BingoResult::Bingo { board_no, score } => {
+                assert_eq!(board_no, 2);
+                assert_eq!(score, 1924);
+            }
+            _ => panic!("bingo expected"),
+        };
+    }
+
+    #[test]
+    fn test_board_dimensions_are_inferred() {
+        let input = "\
+            1,5,9,2,3
+
+            1 2 3
+            4 5 6
+            7 8 9
+
+            1 2
+            3 4
+            5 6
+            ";
+        let (boards, drawn_numbers) = parse(input).unwrap();
+        let dimensions: Vec<_> = boards.iter().map(|b| (b.n_rows(), b.n_cols())).collect();
+        assert_eq!(dimensions, vec![(3, 3), (3, 2)]);
+        // The 3x2 board completes its first row with 2. The 3x3 board completes a diagonal
+        // with 9, which doesn't count, and its first row with 3:
+        match play_to_win(boards.clone(), drawn_numbers.clone(), STANDARD) {
+            BingoResult::Bingo { board_no, score } => {
+                assert_eq!(board_no, 2);
+                assert_eq!(score, (3 + 4 + 6) * 2);
+            }
+            _ => panic!("bingo expected"),
+        };
+        match play_to_lose(boards, drawn_numbers, STANDARD) {
+            BingoResult::Bingo { board_no, score } => {
+                assert_eq!(board_no, 1);
+                assert_eq!(score, (4 + 6 + 7 + 8) * 3);
+            }
+            _ => panic!("bingo expected"),
+        };
+    }
+
+    #[test]
+    fn test_ragged_boards_are_rejected() {
+        let err = parse("1,2\n\n1 2 3\n4 5 6 7\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 7));
+        assert_eq!(
+            err.message,
+            "board 1: expected 3 numbers per row, like on line 3"
+        );
+        let err = parse("1,2\n\n1 2 3\n4 5\n7 8 9\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.text, "4 5");
+    }
+
+    #[test]
+    fn test_win_patterns() {
+        let input = "\
+            1,5,9,3,7,8,4,2,6
+
+            1 2 3
+            4 5 6
+            7 8 9
+            ";
+        let (boards, drawn_numbers) = parse(input).unwrap();
+        let play = |patterns: &[&dyn WinPattern]| match play_to_win(
+            boards.clone(),
+            drawn_numbers.clone(),
+            patterns,
+        ) {
+            BingoResult::Bingo { score, .. } => Some(score),
+            BingoResult::NoBingo => None,
+        };
+        // The rows and columns are only complete after 8:
+        assert_eq!(play(STANDARD), Some((2 + 4 + 6) * 8));
+        assert_eq!(play(&[&Diagonals]), Some((2 + 3 + 4 + 6 + 7 + 8) * 9));
+        assert_eq!(play(&[&FourCorners]), Some((2 + 4 + 6 + 8) * 7));
+        assert_eq!(play(&[&X]), Some((2 + 4 + 6 + 8) * 7));
+        assert_eq!(play(&[&Blackout]), Some(0));
+        let t = Mask(vec![
+            vec![true, true, true],
+            vec![false, true, false],
+            vec![false, true, false],
+        ]);
+        assert_eq!(play(&[&t]), Some(6 * 2));
+        // A mask larger than the board never wins:
+        assert_eq!(play(&[&Mask(vec![vec![true; 4]])]), None);
+        // Neither does a mask without fields:
+        assert_eq!(play(&[&Mask(vec![])]), None);
+        assert_eq!(play(&[&Mask(vec![vec![false; 3]; 3])]), None);
+    }
+
+    #[test]
+    fn test_finishing_order() {
+        let (boards, drawn_numbers) = parse(INPUT).unwrap();
+        let finishing_order = BingoGame::new(boards, drawn_numbers, STANDARD).finishing_order();
+        let boards: Vec<_> = finishing_order.iter().map(|c| c.board_no).collect();
+        assert_eq!(boards, vec![3, 1, 2]);
+        assert_eq!(finishing_order[0].score, 4512);
+        // The first row of the third board:
+        assert_eq!(
+            finishing_order[0].line,
+            vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4)]
+        );
+        assert_eq!(finishing_order[2].score, 1924);
+    }
+
+    #[test]
+    fn test_draw_events() {
+        let (boards, drawn_numbers) = parse(INPUT).unwrap();
+        let mut game = BingoGame::new(boards, drawn_numbers, STANDARD);
+        assert_eq!(
+            game.next().unwrap().to_string(),
+            "draw 1: 7, marked on boards 1, 2, 3"
+        );
+        let event = game.nth(10).unwrap();
+        assert_eq!(
+            event.to_string(),
+            "draw 12: 24, marked on boards 1, 2, 3; \
+             BINGO on board 3 with (0,0) (0,1) (0,2) (0,3) (0,4), score 4512"
+        );
+    }
+
+    #[test]
+    fn test_scoring() {
+        let scores = |scoring| {
+            let (boards, drawn_numbers) = parse(INPUT).unwrap();
+            BingoGame::new(boards, drawn_numbers, STANDARD)
+                .with_scoring(scoring)
+                .finishing_order()
+                .iter()
+                .map(|c| c.score)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(scores(Scoring::UnmarkedSumTimesLastNumber)[0], 4512);
+        assert_eq!(scores(Scoring::UnmarkedSum)[0], 188);
+        assert_eq!(
+            scores(Scoring::LineSumTimesLastNumber)[0],
+            (14 + 21 + 17 + 24 + 4) * 24
+        );
+    }
+
+    #[test]
+    fn test_invalid_boards_are_rejected() {
+        let err = parse("1,2\n\n1 2\n3 4\n\n5 6\n7 5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (7, 3));
+        assert_eq!(err.message, "board 2: duplicate number, already on line 6");
+        let err = parse("1,2\n\n1 2\n3 4\n\n5 6\n7\n").unwrap_err();
+        assert_eq!((err.line, err.column), (7, 1));
+        assert_eq!(
+            err.message,
+            "board 2: expected 2 numbers per row, like on line 6"
+        );
+    }
+
+    #[test]
+    fn test_large_numbers_dont_overflow_the_score() {
+        let (boards, drawn_numbers) = parse("5,100000\n\n5 100000\n300000 300001\n").unwrap();
+        match play_to_win(boards, drawn_numbers, STANDARD) {
+            BingoResult::Bingo { score, .. } => assert_eq!(score, 600_001 * 100_000),
+            _ => panic!("bingo expected"),
+        };
+    }
+}
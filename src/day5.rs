@@ -1,32 +1,68 @@
 use std::cmp;
 use std::fmt::Display;
-use std::fs;
 
-fn main() {
-    let input = fs::read_to_string("./input/day5.txt").expect("failed to read input file");
-    let lines = parse(&input);
-    let n_dangerous_areas = n_dangerous_areas(&lines);
-    println!("There are {} dangerous areas.", n_dangerous_areas);
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(lines: &Self::Input) -> anyhow::Result<usize> {
+        // Only consider horizontal and vertical lines:
+        let lines: Vec<Line> = lines
+            .iter()
+            .filter(|line| {
+                matches!(
+                    line.alignment(),
+                    Alignment::Horizontal | Alignment::Vertical
+                )
+            })
+            .copied()
+            .collect();
+        Ok(n_dangerous_areas(&lines))
+    }
+
+    fn part2(lines: &Self::Input) -> anyhow::Result<usize> {
+        Ok(n_dangerous_areas(lines))
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Coord {
-    x: usize,
-    y: usize,
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
 }
 
 impl Coord {
-    fn from(s: &str) -> Self {
+    /// `s` is a slice of `line`, the line on which the coordinate is found.
+    pub fn from(line_no: usize, line: &str, s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
         let nums: Vec<usize> = s
-            .trim()
             .split(',')
-            .map(|s| s.trim().parse::<usize>().unwrap())
-            .collect();
-        assert_eq!(nums.len(), 2);
-        Self {
+            .map(|n| parse::number(line_no, line, n.trim()))
+            .collect::<Result<_, _>>()?;
+        if nums.len() != 2 {
+            return Err(ParseError::new(
+                line_no,
+                line,
+                s,
+                "expected a coordinate like `x,y`",
+            ));
+        }
+        Ok(Self {
             x: nums[0],
             y: nums[1],
-        }
+        })
     }
 }
 
@@ -37,13 +73,32 @@ impl Display for Coord {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Line(Coord, Coord);
+pub struct Line(pub Coord, pub Coord);
 
 impl Line {
-    fn from(s: &str) -> Self {
-        let coords: Vec<Coord> = s.trim().split("->").map(Coord::from).collect();
-        assert_eq!(coords.len(), 2);
-        Self(coords[0], coords[1])
+    pub fn from(line_no: usize, line: &str) -> Result<Self, ParseError> {
+        let coords: Vec<Coord> = line
+            .split("->")
+            .map(|s| Coord::from(line_no, line, s))
+            .collect::<Result<_, _>>()?;
+        if coords.len() != 2 {
+            return Err(ParseError::new(
+                line_no,
+                line,
+                line.trim(),
+                "expected a line like `x1,y1 -> x2,y2`",
+            ));
+        }
+        let (Coord { x: x0, y: y0 }, Coord { x: x1, y: y1 }) = (coords[0], coords[1]);
+        if x0 != x1 && y0 != y1 && x0.abs_diff(x1) != y0.abs_diff(y1) {
+            return Err(ParseError::new(
+                line_no,
+                line,
+                line.trim(),
+                "expected a horizontal, vertical or 45° diagonal line",
+            ));
+        }
+        Ok(Self(coords[0], coords[1]))
     }
 
     fn max_x(&self) -> usize {
@@ -68,8 +123,8 @@ impl Line {
             (Coord { y: y0, .. }, Coord { y: y1, .. }) if y0 == y1 => Horizontal,
             (Coord { x: x0, .. }, Coord { x: x1, .. }) if x0 == x1 => Vertical,
             (Coord { x: x0, y: y0 }, Coord { x: x1, y: y1 }) => {
-                let x_diff = if x1 > x0 { x1 - x0 } else { x0 - x1 };
-                let y_diff = if y1 > y0 { y1 - y0 } else { y0 - y1 };
+                let x_diff = x1.abs_diff(x0);
+                let y_diff = y1.abs_diff(y0);
                 if x_diff != y_diff {
                     panic!("Not a 45% diagonal line: {}", self);
                 }
@@ -83,7 +138,7 @@ impl Line {
         }
     }
 
-    fn points(&self) -> Vec<Coord> {
+    pub fn points(&self) -> Vec<Coord> {
         use Alignment::*;
         match self.alignment() {
             Horizontal => {
@@ -161,11 +216,13 @@ enum Alignment {
     BottomRightTopLeft,
 }
 
-fn parse(input: &str) -> Vec<Line> {
-    input.trim().lines().map(Line::from).collect()
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    numbered_lines(input)
+        .map(|(line_no, line)| Line::from(line_no, line))
+        .collect()
 }
 
-fn n_dangerous_areas(lines: &[Line]) -> usize {
+pub fn n_dangerous_areas(lines: &[Line]) -> usize {
     // Dangerous areas are points where two or more lines overlap.
 
     // How large is the playing field?
@@ -181,7 +238,7 @@ fn n_dangerous_areas(lines: &[Line]) -> usize {
     for line in lines {
         let points = line.points();
         for point in points {
-            diagram[point.x][point.y] += 1;
+            diagram[point.y][point.x] += 1;
         }
     }
 
@@ -209,15 +266,21 @@ mod test {
         5,5 -> 8,2
         ";
 
+    #[test]
+    fn test_dangerous_areas_without_diagonals() {
+        let lines = parse(INPUT).unwrap();
+        assert_eq!(Day5::part1(&lines).unwrap(), 5);
+    }
+
     #[test]
     fn test_dangerous_areas() {
-        let lines = parse(INPUT);
+        let lines = parse(INPUT).unwrap();
         assert_eq!(n_dangerous_areas(&lines), 12);
     }
 
     #[test]
     fn test_horizontal_line() {
-        let line = Line::from("0,9 -> 5,9");
+        let line = Line::from(1, "0,9 -> 5,9").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -234,7 +297,7 @@ mod test {
 
     #[test]
     fn test_vertical_line() {
-        let line = Line::from("5,0 -> 5,5");
+        let line = Line::from(1, "5,0 -> 5,5").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -251,7 +314,7 @@ mod test {
 
     #[test]
     fn test_diagonal_line_tlbr() {
-        let line = Line::from("0,0 -> 5,5");
+        let line = Line::from(1, "0,0 -> 5,5").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -268,7 +331,7 @@ mod test {
 
     #[test]
     fn test_diagonal_line_bltr() {
-        let line = Line::from("0,5 -> 5,0");
+        let line = Line::from(1, "0,5 -> 5,0").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -285,7 +348,7 @@ mod test {
 
     #[test]
     fn test_diagonal_line_trbl() {
-        let line = Line::from("5,0 -> 0,5");
+        let line = Line::from(1, "5,0 -> 0,5").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -302,7 +365,7 @@ mod test {
 
     #[test]
     fn test_diagonal_line_brtl() {
-        let line = Line::from("5,5 -> 0,0");
+        let line = Line::from(1, "5,5 -> 0,0").unwrap();
         let points = line.points();
         assert_eq!(
             points,
@@ -316,4 +379,21 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_error_for_crooked_line() {
+        let err = parse("0,9 -> 5,9\n8,0 -> 1,8\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.text, "8,0 -> 1,8");
+    }
+
+    #[test]
+    fn test_non_square_diagram() {
+        // Wider than high:
+        let lines = parse("0,0 -> 4,0\n2,0 -> 6,0\n").unwrap();
+        assert_eq!(n_dangerous_areas(&lines), 3);
+        // Higher than wide:
+        let lines = parse("0,0 -> 0,4\n0,2 -> 0,6\n").unwrap();
+        assert_eq!(n_dangerous_areas(&lines), 3);
+    }
 }
@@ -1,3 +1,66 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Write},
+    iter,
+};
+
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedMul, One, Zero};
+
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Lanternfish>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input, SpawnRule::default())?)
+    }
+
+    fn part1(lanternfish: &Self::Input) -> anyhow::Result<usize> {
+        Ok(advance3(lanternfish, 80, SpawnRule::default()))
+    }
+
+    fn part2(lanternfish: &Self::Input) -> anyhow::Result<usize> {
+        Ok(advance3(lanternfish, 256, SpawnRule::default()))
+    }
+}
+
+/// How a species of fish reproduces.
+///
+/// When a fish's timer is 0, it is reset to `reset_timer` the next day, and the fish gives
+/// birth to `litter_size` new fish with a timer of `newborn_timer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnRule {
+    pub reset_timer: usize,
+    pub newborn_timer: usize,
+    pub litter_size: usize,
+}
+
+impl SpawnRule {
+    pub fn max_timer(&self) -> usize {
+        self.reset_timer.max(self.newborn_timer)
+    }
+}
+
+impl Default for SpawnRule {
+    /// The lanternfish from the puzzle.
+    fn default() -> Self {
+        Self {
+            reset_timer: 6,
+            newborn_timer: 8,
+            litter_size: 1,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Lanternfish {
     pub timer: usize,
@@ -5,14 +68,21 @@ pub struct Lanternfish {
 
 impl Lanternfish {
     pub fn new() -> Self {
-        Self { timer: 8 }
+        Self::newborn(SpawnRule::default())
+    }
+
+    pub fn newborn(rule: SpawnRule) -> Self {
+        Self {
+            timer: rule.newborn_timer,
+        }
     }
 
-    fn advance_a_day(&mut self) -> Option<Lanternfish> {
+    /// Returns a baby fish if it's time; there are `rule.litter_size` of those.
+    fn advance_a_day(&mut self, rule: SpawnRule) -> Option<Lanternfish> {
         match self.timer {
             0 => {
-                self.timer = 6;
-                Some(Lanternfish { timer: 8 })
+                self.timer = rule.reset_timer;
+                Some(Lanternfish::newborn(rule))
             }
             _ => {
                 self.timer -= 1;
@@ -28,12 +98,20 @@ impl Default for Lanternfish {
     }
 }
 
-pub fn parse(input: &str) -> Vec<Lanternfish> {
-    input
-        .trim()
-        .split(',')
-        .map(|n| Lanternfish {
-            timer: n.parse::<usize>().unwrap(),
+pub fn parse(input: &str, rule: SpawnRule) -> Result<Vec<Lanternfish>, ParseError> {
+    numbered_lines(input)
+        .flat_map(|(line_no, line)| line.split(',').map(move |n| (line_no, line, n.trim())))
+        .map(|(line_no, line, n)| {
+            let timer = parse::number(line_no, line, n)?;
+            if timer > rule.max_timer() {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    n,
+                    format!("expected a timer between 0 and {}", rule.max_timer()),
+                ));
+            }
+            Ok(Lanternfish { timer })
         })
         .collect()
 }
@@ -44,12 +122,16 @@ pub fn parse(input: &str) -> Vec<Lanternfish> {
 /// iteration we add the new fish to the list of existing fish.
 ///
 /// This works for up to 150 lanternfish, but takes way too long for 256 fish.
-pub fn advance1(mut lanternfish: Vec<Lanternfish>, n_days: usize) -> Vec<Lanternfish> {
+pub fn advance1(
+    mut lanternfish: Vec<Lanternfish>,
+    n_days: usize,
+    rule: SpawnRule,
+) -> Vec<Lanternfish> {
     for _ in 0..n_days {
         let mut new_fish = vec![];
         for fish in &mut lanternfish {
-            if let Some(baby_fish) = fish.advance_a_day() {
-                new_fish.push(baby_fish);
+            if let Some(baby_fish) = fish.advance_a_day(rule) {
+                new_fish.extend(iter::repeat(baby_fish).take(rule.litter_size));
             }
         }
         lanternfish.append(&mut new_fish);
@@ -66,13 +148,21 @@ pub fn advance1(mut lanternfish: Vec<Lanternfish>, n_days: usize) -> Vec<Lantern
 /// - not relying on vector's own resizing for capacity increase
 ///
 /// Turns out, this is actually _slower_ than advance1! Try `cargo bench` to see the results.
-pub fn advance2(mut lanternfish: Vec<Lanternfish>, n_days: usize) -> Vec<Lanternfish> {
+pub fn advance2(
+    mut lanternfish: Vec<Lanternfish>,
+    n_days: usize,
+    rule: SpawnRule,
+) -> Vec<Lanternfish> {
     for _ in 0..n_days {
-        let n_new_fish = lanternfish.iter().filter(|f| f.timer == 0).count();
+        let n_new_fish = lanternfish.iter().filter(|f| f.timer == 0).count() * rule.litter_size;
         for fish in &mut lanternfish {
-            fish.timer = if fish.timer > 0 { fish.timer - 1 } else { 6 };
+            fish.timer = if fish.timer > 0 {
+                fish.timer - 1
+            } else {
+                rule.reset_timer
+            };
         }
-        lanternfish.resize(lanternfish.len() + n_new_fish, Lanternfish::new());
+        lanternfish.resize(lanternfish.len() + n_new_fish, Lanternfish::newborn(rule));
     }
     lanternfish
 }
@@ -83,22 +173,320 @@ pub fn advance2(mut lanternfish: Vec<Lanternfish>, n_days: usize) -> Vec<Lantern
 /// encodes the logic behind creating offsprings.
 ///
 /// This is by a magnitude faster than the other two attempts and easily solves the 256-case.
-pub fn advance3(lanternfish: &[Lanternfish], n_days: usize) -> usize {
-    let mut counts: Vec<usize> = vec![0; 9];
+///
+/// Panics if the population doesn't fit in a usize; advance_matrix can count any population.
+pub fn advance3(lanternfish: &[Lanternfish], n_days: usize, rule: SpawnRule) -> usize {
+    let mut counts = histogram_of(lanternfish, rule);
+    for _ in 0..n_days {
+        advance_counts_a_day(&mut counts, rule).expect("population overflow");
+    }
+    total_of(&counts).expect("population overflow")
+}
+
+/// The number of fish for each timer value, starting with timer 0.
+pub type Histogram = Vec<usize>;
+
+/// The number of timer values to count; fish that don't follow the rule yet may have timers
+/// beyond its own.
+fn n_timers(lanternfish: &[Lanternfish], rule: SpawnRule) -> usize {
+    let max_fish_timer = lanternfish.iter().map(|fish| fish.timer).max().unwrap_or(0);
+    rule.max_timer().max(max_fish_timer) + 1
+}
+
+pub fn histogram_of(lanternfish: &[Lanternfish], rule: SpawnRule) -> Histogram {
+    let mut counts = vec![0; n_timers(lanternfish, rule)];
     for fish in lanternfish {
         counts[fish.timer] += 1;
     }
-    for _ in 0..n_days {
-        let zero_timer_fish = counts[0];
-        for i in 0..8 {
-            counts[i] = counts[i + 1];
+    counts
+}
+
+/// Returns `None` on overflow.
+fn advance_counts_a_day(counts: &mut Histogram, rule: SpawnRule) -> Option<()> {
+    let max_timer = counts.len() - 1;
+    let zero_timer_fish = counts[0];
+    for i in 0..max_timer {
+        counts[i] = counts[i + 1];
+    }
+    counts[max_timer] = 0;
+    // All fish with count==0 produce baby fish with the newborn timer:
+    let newborn_fish = zero_timer_fish.checked_mul(rule.litter_size)?;
+    counts[rule.newborn_timer] = counts[rule.newborn_timer].checked_add(newborn_fish)?;
+    // After that, their timer is reset:
+    counts[rule.reset_timer] = counts[rule.reset_timer].checked_add(zero_timer_fish)?;
+    Some(())
+}
+
+/// The number of all fish in the histogram; `None` on overflow.
+fn total_of(counts: &Histogram) -> Option<usize> {
+    counts
+        .iter()
+        .try_fold(0usize, |total, &n| total.checked_add(n))
+}
+
+/// Like advance3, but yields the histogram of every day, starting with day 0.
+///
+/// The iterator only ends with the first day whose population doesn't fit in a usize; for
+/// the puzzle's rule and a few hundred fish that's somewhere after day 400. Use `take` to
+/// limit the number of days.
+pub fn history(lanternfish: &[Lanternfish], rule: SpawnRule) -> History {
+    History {
+        counts: Some(histogram_of(lanternfish, rule)),
+        rule,
+    }
+}
+
+pub struct History {
+    /// `None` once the population has overflowed.
+    counts: Option<Histogram>,
+    rule: SpawnRule,
+}
+
+impl Iterator for History {
+    type Item = Histogram;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let today = self.counts.take()?;
+        total_of(&today)?;
+        let mut tomorrow = today.clone();
+        if advance_counts_a_day(&mut tomorrow, self.rule).is_some() {
+            self.counts = Some(tomorrow);
         }
-        // All fish with count==0 produce a baby fish with a timer of 8:
-        counts[8] = zero_timer_fish;
-        // After that, their timer is reset to 6:
-        counts[6] += zero_timer_fish;
+        Some(today)
     }
-    counts.iter().sum()
+}
+
+/// Writes the histograms of day 0 up to and including `n_days` as CSV.
+///
+/// There is one row per day, with a column per timer value and the total. Fails with a
+/// PopulationOverflow if the population of a day doesn't fit in a usize.
+pub fn write_history_csv(
+    mut out: impl Write,
+    lanternfish: &[Lanternfish],
+    n_days: usize,
+    rule: SpawnRule,
+) -> io::Result<()> {
+    write!(out, "day")?;
+    for timer in 0..n_timers(lanternfish, rule) {
+        write!(out, ",timer{}", timer)?;
+    }
+    writeln!(out, ",total")?;
+    let mut history = history(lanternfish, rule);
+    for day in 0..=n_days {
+        let counts = history
+            .next()
+            .ok_or_else(|| io::Error::other(PopulationOverflow { n_days: day }))?;
+        write!(out, "{}", day)?;
+        for count in &counts {
+            write!(out, ",{}", count)?;
+        }
+        writeln!(out, ",{}", counts.iter().sum::<usize>())?;
+    }
+    Ok(())
+}
+
+/// Finds initial schools of `school_size` fish that grow to exactly `target` fish after
+/// `n_days`, returning at most `limit` of them as histograms.
+///
+/// In the counting model of advance3, every fish evolves independently of the others, so
+/// the final population is the sum of what each initial fish grows into. Knowing how many
+/// descendants a single fish with timer t has after `n_days`, we're left with choosing how
+/// many fish get which timer, which we search for depth-first, skipping branches that can't
+/// reach the target with the fish that are left.
+pub fn find_initial_timers(
+    target: usize,
+    n_days: usize,
+    school_size: usize,
+    rule: SpawnRule,
+    limit: usize,
+) -> Vec<Histogram> {
+    // The population grown from a single fish per timer; `None` if it exceeds any target.
+    let weights: Vec<Option<usize>> = (0..=rule.max_timer())
+        .map(|timer| {
+            advance_matrix_checked(&[Lanternfish { timer }], n_days, rule)
+                .ok()
+                .and_then(|n| usize::try_from(n).ok())
+        })
+        .collect();
+    let mut search = TimerSearch {
+        weights,
+        limit,
+        counts: Vec::new(),
+        found: Vec::new(),
+    };
+    search.run(0, school_size, target);
+    search.found
+}
+
+struct TimerSearch {
+    weights: Vec<Option<usize>>,
+    limit: usize,
+    counts: Histogram,
+    found: Vec<Histogram>,
+}
+
+impl TimerSearch {
+    /// Distributes `n_fish` over the timers starting at `timer`, such that they grow to
+    /// `target` fish.
+    fn run(&mut self, timer: usize, n_fish: usize, target: usize) {
+        if self.found.len() >= self.limit {
+            return;
+        }
+        if timer == self.weights.len() {
+            if n_fish == 0 && target == 0 {
+                self.found.push(self.counts.clone());
+            }
+            return;
+        }
+        if !self.is_reachable(timer, n_fish, target) {
+            return;
+        }
+        let max_count = match self.weights[timer] {
+            Some(0) => n_fish,
+            Some(weight) => n_fish.min(target / weight),
+            None => 0,
+        };
+        for count in 0..=max_count {
+            let weight = self.weights[timer].unwrap_or(0);
+            self.counts.push(count);
+            self.run(timer + 1, n_fish - count, target - count * weight);
+            self.counts.pop();
+        }
+    }
+
+    /// Whether `n_fish` with timers from `timer` onwards could possibly grow to `target`.
+    fn is_reachable(&self, timer: usize, n_fish: usize, target: usize) -> bool {
+        let weights = self.weights[timer..].iter().flatten();
+        match (weights.clone().min(), weights.max()) {
+            (Some(&min), Some(&max)) => {
+                n_fish.saturating_mul(min) <= target && target <= n_fish.saturating_mul(max)
+            }
+            // None of the remaining timers fit any target:
+            _ => n_fish == 0 && target == 0,
+        }
+    }
+}
+
+/// Like advance3, but in O(log(n_days)) steps and without overflowing.
+///
+/// Advancing the fish counts by one day is a linear transformation, so advancing them by
+/// n days means applying the day's transition matrix n times. We raise the matrix to the
+/// n-th power by repeated squaring, which makes even millions of days feasible.
+pub fn advance_matrix(lanternfish: &[Lanternfish], n_days: usize, rule: SpawnRule) -> BigUint {
+    // Big integers can't overflow:
+    population_after::<BigUint>(lanternfish, n_days, rule).unwrap()
+}
+
+/// Like advance_matrix, but counts using u128 and reports if that's not enough.
+///
+/// Because the fish are counted by timer, this may report an overflow for populations
+/// just below `u128::MAX`.
+pub fn advance_matrix_checked(
+    lanternfish: &[Lanternfish],
+    n_days: usize,
+    rule: SpawnRule,
+) -> Result<u128, PopulationOverflow> {
+    population_after::<u128>(lanternfish, n_days, rule).ok_or(PopulationOverflow { n_days })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PopulationOverflow {
+    pub n_days: usize,
+}
+
+impl fmt::Display for PopulationOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the lanternfish population after {} days is too large to count",
+            self.n_days
+        )
+    }
+}
+
+impl Error for PopulationOverflow {}
+
+type Matrix<T> = Vec<Vec<T>>;
+
+/// Returns `None` on overflow.
+fn population_after<T>(lanternfish: &[Lanternfish], n_days: usize, rule: SpawnRule) -> Option<T>
+where
+    T: Clone + Zero + One + CheckedAdd + CheckedMul + From<u64>,
+{
+    let size = n_timers(lanternfish, rule);
+    let mut counts = vec![T::zero(); size];
+    for fish in lanternfish {
+        counts[fish.timer] = counts[fish.timer].checked_add(&T::one())?;
+    }
+    let transitions = checked_pow(&transition_matrix::<T>(rule, size)?, n_days)?;
+    let mut population = T::zero();
+    for row in &transitions {
+        for (n_transitions, count) in row.iter().zip(&counts) {
+            population = population.checked_add(&n_transitions.checked_mul(count)?)?;
+        }
+    }
+    Some(population)
+}
+
+/// Maps the fish counts by timer of one day to those of the next day; `size` is the number
+/// of timer values, at least `rule.max_timer() + 1`.
+fn transition_matrix<T>(rule: SpawnRule, size: usize) -> Option<Matrix<T>>
+where
+    T: Clone + Zero + One + CheckedAdd + From<u64>,
+{
+    let mut matrix = vec![vec![T::zero(); size]; size];
+    // All timers count down by one..
+    for timer in 0..size - 1 {
+        matrix[timer][timer + 1] = T::one();
+    }
+    // ..except for the fish with a timer of 0: their timer is reset and each of them
+    // produces baby fish with the newborn timer.
+    matrix[rule.reset_timer][0] = T::one();
+    let litter_size = T::from(rule.litter_size as u64);
+    matrix[rule.newborn_timer][0] = matrix[rule.newborn_timer][0].checked_add(&litter_size)?;
+    Some(matrix)
+}
+
+fn checked_pow<T>(matrix: &Matrix<T>, mut exp: usize) -> Option<Matrix<T>>
+where
+    T: Clone + Zero + One + CheckedAdd + CheckedMul,
+{
+    let n = matrix.len();
+    let mut result = vec![vec![T::zero(); n]; n];
+    for (i, row) in result.iter_mut().enumerate() {
+        row[i] = T::one();
+    }
+    let mut base = matrix.clone();
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = checked_mul(&result, &base)?;
+        }
+        exp >>= 1;
+        // Only square if we still need it; it might overflow otherwise.
+        if exp > 0 {
+            base = checked_mul(&base, &base)?;
+        }
+    }
+    Some(result)
+}
+
+fn checked_mul<T>(a: &Matrix<T>, b: &Matrix<T>) -> Option<Matrix<T>>
+where
+    T: Clone + Zero + CheckedAdd + CheckedMul,
+{
+    let n = a.len();
+    let mut product = vec![vec![T::zero(); n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                if a[i][k].is_zero() || b[k][j].is_zero() {
+                    continue;
+                }
+                product[i][j] = product[i][j].checked_add(&a[i][k].checked_mul(&b[k][j])?)?;
+            }
+        }
+    }
+    Some(product)
 }
 
 #[cfg(test)]
@@ -109,28 +497,217 @@ mod test {
 
     #[test]
     fn test_advance1() {
-        let lanternfish = parse(INPUT);
-        let lanternfish = advance1(lanternfish, 18);
+        let lanternfish = parse(INPUT, SpawnRule::default()).unwrap();
+        let lanternfish = advance1(lanternfish, 18, SpawnRule::default());
         assert_eq!(lanternfish.len(), 26);
-        let lanternfish = advance1(lanternfish, 80 - 18);
+        let lanternfish = advance1(lanternfish, 80 - 18, SpawnRule::default());
         assert_eq!(lanternfish.len(), 5934);
     }
 
     #[test]
     fn test_advance2() {
-        let lanternfish = parse(INPUT);
-        let lanternfish = advance2(lanternfish, 18);
+        let lanternfish = parse(INPUT, SpawnRule::default()).unwrap();
+        let lanternfish = advance2(lanternfish, 18, SpawnRule::default());
         assert_eq!(lanternfish.len(), 26);
-        let lanternfish = advance2(lanternfish, 80 - 18);
+        let lanternfish = advance2(lanternfish, 80 - 18, SpawnRule::default());
         assert_eq!(lanternfish.len(), 5934);
     }
 
     #[test]
     fn test_advance3() {
-        let lanternfish = parse(INPUT);
-        let n_lanternfish = advance3(&lanternfish, 18);
+        let lanternfish = parse(INPUT, SpawnRule::default()).unwrap();
+        let n_lanternfish = advance3(&lanternfish, 18, SpawnRule::default());
         assert_eq!(n_lanternfish, 26);
-        let n_lanternfish = advance3(&lanternfish, 80);
+        let n_lanternfish = advance3(&lanternfish, 80, SpawnRule::default());
         assert_eq!(n_lanternfish, 5934);
     }
+
+    #[test]
+    fn test_parse_rejects_timer_out_of_range() {
+        let err = parse("3,4,9,1,2", SpawnRule::default()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+    }
+
+    #[test]
+    fn test_advance_matrix() {
+        let lanternfish = parse(INPUT, SpawnRule::default()).unwrap();
+        for n_days in [0, 1, 18, 80, 256] {
+            let n_lanternfish = advance3(&lanternfish, n_days, SpawnRule::default());
+            assert_eq!(
+                advance_matrix(&lanternfish, n_days, SpawnRule::default()),
+                n_lanternfish.into()
+            );
+            assert_eq!(
+                advance_matrix_checked(&lanternfish, n_days, SpawnRule::default()),
+                Ok(n_lanternfish as u128)
+            );
+        }
+    }
+
+    #[test]
+    fn test_advance_matrix_beyond_u128() {
+        let lanternfish = parse(INPUT, SpawnRule::default()).unwrap();
+        assert_eq!(
+            advance_matrix_checked(&lanternfish, 2000, SpawnRule::default()),
+            Err(PopulationOverflow { n_days: 2000 })
+        );
+        // The population at least doubles every 9 days:
+        let lower_bound = BigUint::from(lanternfish.len()) << (2000 / 9);
+        assert!(advance_matrix(&lanternfish, 2000, SpawnRule::default()) > lower_bound);
+    }
+
+    #[test]
+    fn test_strategies_agree_on_custom_spawn_rules() {
+        let rules = [
+            SpawnRule::default(),
+            SpawnRule {
+                reset_timer: 3,
+                newborn_timer: 5,
+                litter_size: 2,
+            },
+            // Newborns may start with a lower timer than their parents:
+            SpawnRule {
+                reset_timer: 4,
+                newborn_timer: 2,
+                litter_size: 1,
+            },
+            // A species that doesn't reproduce at all:
+            SpawnRule {
+                reset_timer: 2,
+                newborn_timer: 0,
+                litter_size: 0,
+            },
+        ];
+        for rule in rules {
+            let lanternfish = parse("0,1,2", rule).unwrap();
+            for n_days in [0, 1, 7, 20, 40] {
+                let n_lanternfish = advance3(&lanternfish, n_days, rule);
+                assert_eq!(
+                    advance1(lanternfish.clone(), n_days, rule).len(),
+                    n_lanternfish
+                );
+                assert_eq!(
+                    advance2(lanternfish.clone(), n_days, rule).len(),
+                    n_lanternfish
+                );
+                assert_eq!(
+                    advance_matrix(&lanternfish, n_days, rule),
+                    n_lanternfish.into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_strategies_agree_on_timers_beyond_the_rule() {
+        // Parsed for the puzzle's rule, but advanced with a rule of shorter timers:
+        let lanternfish = parse("8", SpawnRule::default()).unwrap();
+        let rule = SpawnRule {
+            reset_timer: 3,
+            newborn_timer: 5,
+            litter_size: 1,
+        };
+        for n_days in [0, 8, 9, 13, 20, 40] {
+            let n_lanternfish = advance1(lanternfish.clone(), n_days, rule).len();
+            assert_eq!(
+                advance2(lanternfish.clone(), n_days, rule).len(),
+                n_lanternfish
+            );
+            assert_eq!(advance3(&lanternfish, n_days, rule), n_lanternfish);
+            assert_eq!(
+                advance_matrix(&lanternfish, n_days, rule),
+                n_lanternfish.into()
+            );
+            let counts = history(&lanternfish, rule).nth(n_days).unwrap();
+            assert_eq!(counts.iter().sum::<usize>(), n_lanternfish);
+        }
+        assert_eq!(advance3(&lanternfish, 9, rule), 2);
+    }
+
+    #[test]
+    fn test_history_matches_advance1_day_by_day() {
+        let rule = SpawnRule::default();
+        let mut lanternfish = parse(INPUT, rule).unwrap();
+        for counts in history(&lanternfish.clone(), rule).take(40) {
+            assert_eq!(counts, histogram_of(&lanternfish, rule));
+            lanternfish = advance1(lanternfish, 1, rule);
+        }
+    }
+
+    #[test]
+    fn test_history_csv() {
+        let rule = SpawnRule::default();
+        let lanternfish = parse("1,0", rule).unwrap();
+        let mut csv = vec![];
+        write_history_csv(&mut csv, &lanternfish, 2, rule).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "\
+day,timer0,timer1,timer2,timer3,timer4,timer5,timer6,timer7,timer8,total
+0,1,1,0,0,0,0,0,0,0,2
+1,1,0,0,0,0,0,1,0,1,3
+2,0,0,0,0,0,1,1,1,1,4
+"
+        );
+    }
+
+    #[test]
+    fn test_history_ends_before_overflowing() {
+        let rule = SpawnRule::default();
+        let lanternfish = parse(INPUT, rule).unwrap();
+        let last = history(&lanternfish, rule).last().unwrap();
+        let n_days = history(&lanternfish, rule).count() - 1;
+        assert_eq!(n_days, 489);
+        assert_eq!(
+            BigUint::from(last.iter().sum::<usize>()),
+            advance_matrix(&lanternfish, n_days, rule)
+        );
+        assert!(advance_matrix(&lanternfish, n_days + 1, rule) > BigUint::from(usize::MAX));
+
+        assert!(write_history_csv(io::sink(), &lanternfish, n_days, rule).is_ok());
+        let err = write_history_csv(io::sink(), &lanternfish, n_days + 1, rule).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the lanternfish population after 490 days is too large to count"
+        );
+    }
+
+    fn school_from(counts: &[usize]) -> Vec<Lanternfish> {
+        counts
+            .iter()
+            .enumerate()
+            .flat_map(|(timer, &count)| iter::repeat(Lanternfish { timer }).take(count))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_initial_timers_finds_example() {
+        let rule = SpawnRule::default();
+        let example = histogram_of(&parse(INPUT, rule).unwrap(), rule);
+        let found = find_initial_timers(26, 18, 5, rule, usize::MAX);
+        assert!(found.contains(&example));
+        for counts in found {
+            let school = school_from(&counts);
+            assert_eq!(school.len(), 5);
+            assert_eq!(advance3(&school, 18, rule), 26);
+        }
+    }
+
+    #[test]
+    fn test_find_initial_timers_without_solution() {
+        let rule = SpawnRule::default();
+        // A single fish never dies out:
+        assert!(find_initial_timers(0, 18, 1, rule, usize::MAX).is_empty());
+        // No school of five grows faster than five fish with timer 0:
+        let max = advance3(&school_from(&[5]), 18, rule);
+        assert!(find_initial_timers(max + 1, 18, 5, rule, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_find_initial_timers_respects_limit() {
+        let rule = SpawnRule::default();
+        // On day 0, every school of 3 fish counts 3 fish:
+        assert_eq!(find_initial_timers(3, 0, 3, rule, usize::MAX).len(), 165);
+        assert_eq!(find_initial_timers(3, 0, 3, rule, 10).len(), 10);
+    }
 }
@@ -0,0 +1,161 @@
+use crate::{
+    parse::{self, numbered_lines},
+    ParseError, Solution,
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(positions: &Self::Input) -> anyhow::Result<usize> {
+        let meeting_point = find_best_meeting_point_linear(positions);
+        Ok(fuel_cost_linear(positions, meeting_point))
+    }
+
+    fn part2(positions: &Self::Input) -> anyhow::Result<usize> {
+        let meeting_point = find_best_meeting_point_increasing(positions);
+        Ok(fuel_cost_increasing(positions, meeting_point))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let positions: Vec<usize> = numbered_lines(input)
+        .flat_map(|(line_no, line)| line.split(',').map(move |x| (line_no, line, x.trim())))
+        .map(|(line_no, line, x)| parse::number(line_no, line, x))
+        .collect::<Result<_, _>>()?;
+    if positions.is_empty() {
+        return Err(ParseError::new(1, "", "", "expected crab positions"));
+    }
+    Ok(positions)
+}
+
+/// Part 1: each step costs one unit of fuel.
+pub fn find_best_meeting_point_linear(positions: &[usize]) -> usize {
+    // The fuel needed is the distance to the target position. To minimize the distance
+    // for all crab submarines, we're looking for the median of all current positions.
+    let sorted = {
+        let mut cloned = positions.to_vec();
+        cloned.sort_unstable();
+        cloned
+    };
+    match sorted.len() {
+        // if length is odd, we return the number in the middle:
+        len if len % 2 != 0 => sorted[len / 2],
+        // if length is even, we round the mean of the two middle numbers to the nearest integer:
+        len => {
+            let a = sorted[len / 2 - 1];
+            let b = sorted[len / 2];
+            ((a as f64 + b as f64) / 2.).round() as usize
+        }
+    }
+}
+
+pub fn fuel_cost_linear(positions: &[usize], meeting_point: usize) -> usize {
+    let mut cost = 0;
+    for pos in positions {
+        cost += abs_diff(*pos, meeting_point)
+    }
+    cost
+}
+
+/// Part 2: each step costs one unit more than the step before.
+pub fn find_best_meeting_point_increasing(positions: &[usize]) -> usize {
+    let highest = *positions.iter().max().expect("no positions");
+    let mut best_point = highest;
+    let mut best_cost = usize::MAX;
+    for meeting_point in 0..=highest {
+        let cost = fuel_cost_increasing(positions, meeting_point);
+        if cost < best_cost {
+            best_point = meeting_point;
+            best_cost = cost;
+        }
+    }
+    best_point
+}
+
+pub fn fuel_cost_increasing(positions: &[usize], meeting_point: usize) -> usize {
+    let mut cost = 0;
+    for pos in positions {
+        let distance = abs_diff(*pos, meeting_point);
+        cost += (1..=distance).sum::<usize>();
+    }
+    cost
+}
+
+fn abs_diff(a: usize, b: usize) -> usize {
+    if a < b {
+        b.wrapping_sub(a)
+    } else {
+        a.wrapping_sub(b)
+    }
+}
+
+#[cfg(test)]
+mod example {
+    use super::*;
+
+    #[test]
+    fn test_meeting_point_linear() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let pos = find_best_meeting_point_linear(&positions);
+        assert_eq!(pos, 2);
+    }
+
+    #[test]
+    fn test_fuel_cost_linear() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let meeting_point = 2;
+        let fuel_cost = fuel_cost_linear(&positions, meeting_point);
+        assert_eq!(fuel_cost, 37);
+    }
+
+    #[test]
+    fn test_meeting_point_increasing() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let pos = find_best_meeting_point_increasing(&positions);
+        assert_eq!(pos, 5);
+    }
+
+    #[test]
+    fn test_fuel_cost_increasing_best_point() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let meeting_point = 5;
+        let fuel_cost = fuel_cost_increasing(&positions, meeting_point);
+        assert_eq!(fuel_cost, 168);
+    }
+
+    #[test]
+    fn test_fuel_cost_increasing_prev_best_point() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let meeting_point = 2;
+        let fuel_cost = fuel_cost_increasing(&positions, meeting_point);
+        assert_eq!(fuel_cost, 206);
+    }
+
+    #[test]
+    fn test_fuel_cost_increasing_individual_moves() {
+        assert_eq!(fuel_cost_increasing(&[16], 5), 66);
+        assert_eq!(fuel_cost_increasing(&[1], 5), 10);
+        assert_eq!(fuel_cost_increasing(&[2], 5), 6);
+        assert_eq!(fuel_cost_increasing(&[0], 5), 15);
+        assert_eq!(fuel_cost_increasing(&[4], 5), 1);
+        assert_eq!(fuel_cost_increasing(&[7], 5), 3);
+        assert_eq!(fuel_cost_increasing(&[14], 5), 45);
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_input() {
+        for input in ["", "\n  \n"] {
+            let err = parse(input).unwrap_err();
+            assert_eq!(err.line, 1);
+            assert!(err.to_string().contains("expected crab positions"));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use itertools::Itertools;
+use std::collections::HashMap;
+
+use crate::{parse::numbered_lines, ParseError, Solution};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(entries: &Self::Input) -> anyhow::Result<usize> {
+        Ok(entries.iter().map(count_easy_digits).sum())
+    }
+
+    fn part2(entries: &Self::Input) -> anyhow::Result<u32> {
+        Ok(entries.iter().map(decode_output).sum())
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Pattern(String);
+
+impl Pattern {
+    fn from(word: &str) -> Self {
+        let mut chars: Vec<_> = word.to_lowercase().chars().collect();
+        chars.sort_unstable();
+        Self(chars.iter().collect())
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn contains(&self, other: &Self) -> bool {
+        other.0.chars().all(|c| self.0.contains(c))
+    }
+}
+
+/// One line of the puzzle input: the ten unique signal patterns and the four-digit output.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// The digit each of the ten signal patterns stands for.
+    digits: HashMap<Pattern, u32>,
+    output: Vec<Pattern>,
+}
+
+impl Entry {
+    pub fn from(line_no: usize, line: &str) -> Result<Self, ParseError> {
+        let (input, output) = line.split('|').collect_tuple().ok_or_else(|| {
+            ParseError::new(
+                line_no,
+                line,
+                line.trim(),
+                "expected signal patterns and output separated by `|`",
+            )
+        })?;
+        let patterns = |s: &str, n: usize| {
+            if s.split_whitespace().count() != n {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    s.trim(),
+                    format!("expected {} patterns", n),
+                ));
+            }
+            if let Some(word) = s
+                .split_whitespace()
+                .find(|w| !w.chars().all(|c| ('a'..='g').contains(&c)))
+            {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    word,
+                    "expected segments `a` to `g`",
+                ));
+            }
+            Ok(s.split_whitespace().map(Pattern::from).collect_vec())
+        };
+        let signal_patterns = patterns(input, 10)?;
+        let output_patterns = patterns(output, 4)?;
+        // The ten digits use 2, 3, 4 and 7 segments once each, and 5 and 6 segments three times
+        // each:
+        let mut lengths: Vec<usize> = signal_patterns.iter().map(Pattern::len).collect();
+        lengths.sort_unstable();
+        if signal_patterns.iter().unique().count() != 10
+            || lengths != [2, 3, 4, 5, 5, 5, 6, 6, 6, 7]
+        {
+            return Err(ParseError::new(
+                line_no,
+                line,
+                input.trim(),
+                "expected the ten distinct patterns of the digits 0 to 9",
+            ));
+        }
+        let digits = digits_of(&signal_patterns).ok_or_else(|| {
+            ParseError::new(
+                line_no,
+                line,
+                input.trim(),
+                "the patterns don't match the segments of the digits 0 to 9",
+            )
+        })?;
+        if let Some(word) = output
+            .split_whitespace()
+            .find(|w| !digits.contains_key(&Pattern::from(w)))
+        {
+            return Err(ParseError::new(
+                line_no,
+                line,
+                word,
+                "expected one of the signal patterns",
+            ));
+        }
+        Ok(Self {
+            digits,
+            output: output_patterns,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+    numbered_lines(input)
+        .map(|(line_no, line)| Entry::from(line_no, line))
+        .collect()
+}
+
+/// Part 1: 1, 4, 7 and 8 are the only digits that use a unique number of segments.
+pub fn count_easy_digits(entry: &Entry) -> usize {
+    entry
+        .output
+        .iter()
+        .filter(|x| matches!(x.len(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+/// Works out which pattern stands for which digit; `None` if that's impossible.
+fn digits_of(input: &[Pattern]) -> Option<HashMap<Pattern, u32>> {
+    let one = input.iter().find(|x| x.len() == 2)?;
+    let four = input.iter().find(|x| x.len() == 4)?;
+    let seven = input.iter().find(|x| x.len() == 3)?;
+    let eight = input.iter().find(|x| x.len() == 7)?;
+    // 6 is the only pattern of length 6 that doesn't contain 1 or 4.
+    let six = input
+        .iter()
+        .filter(|x| x.len() == 6)
+        .find(|x| !x.contains(one) && !x.contains(four))?;
+    // 9 is the only pattern of length 6 that contains 4.
+    let nine = input
+        .iter()
+        .filter(|x| x.len() == 6)
+        .find(|x| x.contains(four))?;
+    // 0 is the only pattern of length 6 that contains 1 but not 4.
+    let zero = input
+        .iter()
+        .filter(|x| x.len() == 6)
+        .find(|x| x.contains(one) && !x.contains(four))?;
+    // 3 is the only pattern of length 5 that contains 1.
+    let three = input
+        .iter()
+        .filter(|x| x.len() == 5)
+        .find(|x| x.contains(one))?;
+    // 2 is the only pattern of length 5 that doesn't contain 1 and also doesn't fit into 6.
+    let two = input
+        .iter()
+        .filter(|x| x.len() == 5)
+        .find(|x| !x.contains(one) && !six.contains(x))?;
+    // 5 is the only pattern of length 5 that doesn't contain 1 and fits into 6.
+    let five = input
+        .iter()
+        .filter(|x| x.len() == 5)
+        .find(|x| !x.contains(one) && six.contains(x))?;
+
+    let digits = HashMap::from([
+        (zero, 0),
+        (one, 1),
+        (two, 2),
+        (three, 3),
+        (four, 4),
+        (five, 5),
+        (six, 6),
+        (seven, 7),
+        (eight, 8),
+        (nine, 9),
+    ]);
+
+    // Each pattern must stand for a different digit:
+    if digits.len() != 10 {
+        return None;
+    }
+    Some(
+        digits
+            .into_iter()
+            .map(|(pattern, digit)| (pattern.clone(), digit))
+            .collect(),
+    )
+}
+
+pub fn decode_output(Entry { digits, output }: &Entry) -> u32 {
+    // Entry::from made sure that every output pattern is known:
+    output.iter().fold(0, |acc, p| acc * 10 + digits[p])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+        be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+    ";
+
+    #[test]
+    fn example_part1() {
+        let n_easy_digits: usize = parse(INPUT).unwrap().iter().map(count_easy_digits).sum();
+        assert_eq!(n_easy_digits, 26);
+    }
+
+    #[test]
+    fn example_part2() {
+        let output_total: u32 = parse(INPUT).unwrap().iter().map(decode_output).sum();
+        assert_eq!(output_total, 61229);
+    }
+
+    #[test]
+    fn test_single_entry() {
+        let line =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        let output = decode_output(&Entry::from(1, line).unwrap());
+        assert_eq!(output, 5353);
+    }
+
+    #[test]
+    fn test_inconsistent_patterns_are_rejected() {
+        let line = "abcdefg abcdefg abcdefg abcdefg abcdefg abcdefg abcdefg abcdefg abcdefg abcdefg | ab ab ab ab";
+        let err = Entry::from(1, line).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(
+            err.message,
+            "expected the ten distinct patterns of the digits 0 to 9"
+        );
+        // The output uses a pattern that isn't among the signal patterns:
+        let line =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb abc";
+        let err = Entry::from(1, line).unwrap_err();
+        assert_eq!(err.text, "abc");
+    }
+}
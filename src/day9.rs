@@ -1,53 +1,80 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-fn main() {
-    let input = fs::read_to_string("./input/day9.txt").expect("failed to read input file");
-    let matrix = parse(&input);
-    let low_points = low_points_of(&matrix);
-    let risk_levels = risk_levels_of(&low_points);
-    println!("[part1] sum: {:?}", risk_levels.iter().sum::<u32>());
-    let basins = basins_in(&matrix);
-    let score = three_largest_basins_size_product(&basins);
-    println!("[part2] score: {:?}", score);
+use crate::{parse::numbered_lines, ParseError, Solution};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Matrix;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(matrix: &Self::Input) -> anyhow::Result<u32> {
+        let low_points = low_points_of(matrix);
+        Ok(risk_levels_of(&low_points).iter().sum())
+    }
+
+    fn part2(matrix: &Self::Input) -> anyhow::Result<usize> {
+        let basins = basins_in(matrix);
+        Ok(three_largest_basins_size_product(&basins))
+    }
 }
 
-type RiskLevel = u32;
+pub type RiskLevel = u32;
 
 #[derive(Debug, Clone, Copy)]
-struct Point {
-    location: (usize, usize),
-    value: u32,
+pub struct Point {
+    pub location: (usize, usize),
+    pub value: u32,
 }
 impl Point {
     fn new(location: (usize, usize), value: u32) -> Self {
         Self { location, value }
     }
 }
-type Matrix = Vec<Vec<Point>>;
-
-fn parse(input: &str) -> Matrix {
-    let mut rows = vec![];
-    for (line_no, line) in input.lines().enumerate() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        let row = line
-            .chars()
-            .map(|c| c.to_digit(10).expect("NaN"))
+pub type Matrix = Vec<Vec<Point>>;
+
+pub fn parse(input: &str) -> Result<Matrix, ParseError> {
+    let mut rows: Matrix = vec![];
+    for (line_no, line) in numbered_lines(input) {
+        let trimmed = line.trim();
+        let y = rows.len();
+        let row = trimmed
+            .char_indices()
             .enumerate()
-            .map(|(pos, val)| Point::new((pos, line_no), val))
-            .collect();
+            .map(|(pos, (i, c))| match c.to_digit(10) {
+                Some(val) => Ok(Point::new((pos, y), val)),
+                None => Err(ParseError::new(
+                    line_no,
+                    line,
+                    &trimmed[i..i + c.len_utf8()],
+                    "expected a digit",
+                )),
+            })
+            .collect::<Result<Vec<Point>, ParseError>>()?;
+        if let Some(first_row) = rows.first() {
+            if row.len() != first_row.len() {
+                return Err(ParseError::new(
+                    line_no,
+                    line,
+                    trimmed,
+                    format!("expected {} digits, like the first row", first_row.len()),
+                ));
+            }
+        }
         rows.push(row);
     }
-    rows
+    Ok(rows)
 }
 
 #[allow(clippy::ptr_arg)]
-fn low_points_of(matrix: &Matrix) -> Vec<Point> {
+pub fn low_points_of(matrix: &Matrix) -> Vec<Point> {
     let mut low_points = vec![];
     for (r, row) in matrix.iter().enumerate() {
         let has_neighbor_above = r > 0;
@@ -77,14 +104,14 @@ fn low_points_of(matrix: &Matrix) -> Vec<Point> {
     low_points
 }
 
-fn risk_levels_of(values: &[Point]) -> Vec<RiskLevel> {
+pub fn risk_levels_of(values: &[Point]) -> Vec<RiskLevel> {
     values.iter().map(|x| x.value + 1).collect()
 }
 
 #[derive(Debug, Default)]
-struct Basin<'a> {
-    points: Vec<&'a Point>,
-    size: usize,
+pub struct Basin<'a> {
+    pub points: Vec<&'a Point>,
+    pub size: usize,
 }
 impl<'a> Basin<'a> {
     fn add(&mut self, point: &'a Point) {
@@ -94,7 +121,7 @@ impl<'a> Basin<'a> {
 }
 
 #[allow(clippy::ptr_arg)]
-fn basins_in(matrix: &Matrix) -> Vec<Basin> {
+pub fn basins_in(matrix: &Matrix) -> Vec<Basin<'_>> {
     // We iterate through all of the points, but we remember and skip those already
     // visited. For each point we visit, we try to extend those the boundaries of the
     // basin (the '9's).
@@ -140,7 +167,7 @@ fn basins_in(matrix: &Matrix) -> Vec<Basin> {
     basins
 }
 
-fn three_largest_basins_size_product(basins: &[Basin]) -> usize {
+pub fn three_largest_basins_size_product(basins: &[Basin]) -> usize {
     basins
         .iter()
         .map(|x| x.size)
@@ -164,7 +191,7 @@ mod test {
 
     #[test]
     fn test_low_points() {
-        let low_points = low_points_of(&parse(INPUT));
+        let low_points = low_points_of(&parse(INPUT).unwrap());
 
         let risk_levels = risk_levels_of(&low_points);
         assert_eq!(risk_levels, vec![2, 1, 6, 6]);
@@ -176,9 +203,16 @@ mod test {
 
     #[test]
     fn test_basins() {
-        let matrix = parse(INPUT);
+        let matrix = parse(INPUT).unwrap();
         let basins = basins_in(&matrix);
         let score = three_largest_basins_size_product(&basins);
         assert_eq!(score, 1134);
     }
+
+    #[test]
+    fn test_parse_error_for_non_digit() {
+        let err = parse("2199943210\n39878x4921\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.text, "x");
+    }
 }
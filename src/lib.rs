@@ -1 +1,18 @@
+pub mod answers;
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
 pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod parse;
+pub mod registry;
+mod solution;
+pub mod timing;
+
+pub use parse::ParseError;
+pub use solution::Solution;
@@ -0,0 +1,291 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use clap::{Parser, Subcommand};
+
+use aoc::{
+    answers::{self, Answers},
+    day1::{self, Aggregate, Comparison},
+    day2::{self, MovementModel},
+    day3,
+    day4::{self, BingoGame},
+    registry::{self, Part, PUZZLES},
+    timing::{self, TimingReport},
+};
+
+#[derive(Parser)]
+#[clap(about = "Solutions to the Advent of Code 2021 puzzles")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzle of the given day.
+    Run {
+        day: u8,
+        /// Only solve this part (1 or 2); solves both parts by default.
+        #[clap(long, possible_values = ["1", "2"])]
+        part: Option<u8>,
+        /// Read the puzzle input from this file instead of `./input/day<DAY>.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// List all puzzles.
+    List,
+    /// Time parsing and solving of all puzzles on their default input files.
+    BenchAll {
+        /// Print the timings as JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Check that all puzzles still produce the answers recorded in `./answers.toml`.
+    Check {
+        /// Record the current answers instead of checking them.
+        #[clap(long)]
+        record: bool,
+    },
+    /// Compare sliding windows of the day 1 sonar sweep.
+    Sonar {
+        /// The number of depths in a window.
+        #[clap(long, default_value = "1", validator = window_size)]
+        window: usize,
+        /// How the depths within a window are combined.
+        #[clap(long, default_value = "sum", possible_values = ["sum", "mean", "max"])]
+        aggregate: Aggregate,
+        /// Only print the number of windows that compare like this.
+        #[clap(long, possible_values = ["increase", "decrease", "unchanged"])]
+        comparison: Option<Comparison>,
+        /// Read the sonar sweep from this file instead of `./input/day1.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// Print the position of the day 2 submarine after every command, as CSV.
+    Trace {
+        /// How the submarine interprets `down` and `up`.
+        #[clap(long, default_value = "aim", possible_values = ["direct", "aim"])]
+        model: MovementModel,
+        /// Print a JSON object per command instead of CSV.
+        #[clap(long)]
+        json: bool,
+        /// Read the course from this file instead of `./input/day2.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// Print statistics of every bit column of the day 3 diagnostic report.
+    Diagnostics {
+        /// Print the statistics as JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+        /// Read the diagnostic report from this file instead of `./input/day3.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// Replay the day 4 bingo game, printing what happens for every drawn number.
+    Replay {
+        /// Read the bingo game from this file instead of `./input/day4.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// Print statistics and spikes of the day 1 sonar sweep.
+    DepthReport {
+        /// The number of preceding depths a depth is compared to when looking for spikes.
+        #[clap(long, default_value = "10", validator = window_size)]
+        window: usize,
+        /// Report depths deviating by more than this many standard deviations as spikes.
+        #[clap(long, default_value = "3")]
+        threshold: f64,
+        /// Read the sonar sweep from this file instead of `./input/day1.txt`.
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn window_size(s: &str) -> Result<(), String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("the window must hold at least one depth".to_owned()),
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    // Invalid arguments make clap exit with code 2.
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Run { day, part, input } => {
+            let puzzle =
+                registry::puzzle(day).ok_or_else(|| anyhow!("no puzzle for day {}", day))?;
+            let path = input.unwrap_or_else(|| puzzle.default_input_path().into());
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input file {}", path.display()))?;
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(2) => vec![Part::Two],
+                _ => vec![Part::One, Part::Two],
+            };
+            let input = puzzle
+                .parse(&input)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            for part in parts {
+                let answer = puzzle.solve_parsed(part, &input)?;
+                println!("part {}: {}", part, answer);
+            }
+        }
+        Command::List => {
+            for puzzle in PUZZLES {
+                println!("day {:>2} {}", puzzle.day, puzzle.title);
+            }
+        }
+        Command::BenchAll { json } => {
+            let report = timing::time_puzzles(PUZZLES)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_timings(&report);
+            }
+        }
+        Command::Check { record: true } => {
+            Answers::record(PUZZLES)?.save(answers::DEFAULT_PATH)?;
+            println!("recorded answers in {}", answers::DEFAULT_PATH);
+        }
+        Command::Check { record: false } => {
+            let answers = Answers::load(answers::DEFAULT_PATH)?;
+            let checks = answers::check(PUZZLES, &answers)?;
+            for check in &checks {
+                println!(
+                    "day {:>2} part {}: {}",
+                    check.day, check.part, check.outcome
+                );
+            }
+            let n_regressions = checks.iter().filter(|c| c.outcome.is_regression()).count();
+            if n_regressions > 0 {
+                bail!("{} answer(s) changed", n_regressions);
+            }
+        }
+        Command::Sonar {
+            window,
+            aggregate,
+            comparison,
+            input,
+        } => match (aggregate, comparison) {
+            // Streams the sweep, so even huge sonar logs don't have to fit into memory:
+            (Aggregate::Sum | Aggregate::Mean, Some(Comparison::Increase)) => {
+                let path = depths_path(input);
+                let file = File::open(&path)
+                    .with_context(|| format!("failed to read input file {}", path.display()))?;
+                let n_increases = day1::count_increases_in(BufReader::new(file), window)
+                    .with_context(|| format!("failed to parse {}", path.display()))?;
+                println!("{}", n_increases);
+            }
+            _ => {
+                let depths = read_depths(input)?;
+                let measurement = day1::measure(&depths, window, aggregate);
+                match comparison {
+                    Some(comparison) => println!("{}", measurement.count(comparison)),
+                    None => {
+                        println!("increased: {}", measurement.increased);
+                        println!("decreased: {}", measurement.decreased);
+                        println!("unchanged: {}", measurement.unchanged);
+                    }
+                }
+            }
+        },
+        Command::Trace { model, json, input } => {
+            let path = input.unwrap_or_else(|| "./input/day2.txt".into());
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input file {}", path.display()))?;
+            let course = day2::parse(&input)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            let stdout = io::stdout().lock();
+            if json {
+                day2::write_trace_json_lines(stdout, &course, model)?;
+            } else {
+                day2::write_trace_csv(stdout, &course, model)?;
+            }
+        }
+        Command::Diagnostics { json, input } => {
+            let path = input.unwrap_or_else(|| "./input/day3.txt".into());
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input file {}", path.display()))?;
+            let report: day3::Input = input
+                .parse()
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            let columns = day3::column_stats(&report);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&columns)?);
+            } else {
+                print!("{}", day3::column_stats_table(&columns));
+            }
+        }
+        Command::Replay { input } => {
+            let path = input.unwrap_or_else(|| "./input/day4.txt".into());
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input file {}", path.display()))?;
+            let (boards, drawn_numbers) = day4::parse(&input)
+                .with_context(|| format!("failed to parse {}", path.display()))?;
+            for event in BingoGame::new(boards, drawn_numbers, day4::STANDARD) {
+                println!("{}", event);
+            }
+        }
+        Command::DepthReport {
+            window,
+            threshold,
+            input,
+        } => {
+            let depths = read_depths(input)?;
+            let report = day1::depth_report(&depths, window, threshold)
+                .ok_or_else(|| anyhow!("the sonar sweep is empty"))?;
+            println!("{}", report);
+        }
+    }
+    Ok(())
+}
+
+fn depths_path(input: Option<PathBuf>) -> PathBuf {
+    input.unwrap_or_else(|| "./input/day1.txt".into())
+}
+
+fn read_depths(input: Option<PathBuf>) -> anyhow::Result<Vec<i32>> {
+    let path = depths_path(input);
+    let input = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read input file {}", path.display()))?;
+    day1::parse(&input).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn print_timings(report: &TimingReport) {
+    let fmt = |duration: Duration| format!("{:.1?}", duration);
+    println!(
+        "{:>3}  {:<24} {:>10} {:>10} {:>10} {:>10}",
+        "day", "title", "parse", "part 1", "part 2", "total"
+    );
+    for timing in &report.puzzles {
+        println!(
+            "{:>3}  {:<24} {:>10} {:>10} {:>10} {:>10}",
+            timing.day,
+            timing.title,
+            fmt(timing.parse),
+            fmt(timing.part1),
+            fmt(timing.part2),
+            fmt(timing.total())
+        );
+    }
+    println!("{:<61} {:>10}", "total", fmt(report.total));
+}
@@ -0,0 +1,118 @@
+//! Helpers shared by the puzzle input parsers.
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// A malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Starts at 1.
+    pub line: usize,
+    /// Starts at 1; counted in characters, not bytes.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Creates an error for `text` on the given line.
+    ///
+    /// `text` is expected to be a slice of `line`, so its column can be derived from it.
+    /// If it's not, the error points at the beginning of the line.
+    pub fn new(line_no: usize, line: &str, text: &str, message: impl Into<String>) -> Self {
+        let line_start = line.as_ptr() as usize;
+        let text_start = text.as_ptr() as usize;
+        let offset =
+            if text_start >= line_start && text_start + text.len() <= line_start + line.len() {
+                text_start - line_start
+            } else {
+                0
+            };
+        Self {
+            line: line_no,
+            column: line[..offset].chars().count() + 1,
+            text: text.to_owned(),
+            message: message.into(),
+            source_line: line.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, ", found `{}`", self.text)?;
+        }
+        // Render the line with the offending text underlined, like a compiler would:
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.text.chars().count().max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Iterates over the non-blank lines of the input, together with their line numbers.
+///
+/// The lines are not trimmed, so that errors can report the correct column.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+}
+
+/// Parses a number that's found at `text` on the given line.
+pub fn number<T: FromStr>(line_no: usize, line: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(line_no, line, text, "expected a number"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_column_is_derived_from_text() {
+        let line = "  12 3x4 56";
+        let err = number::<u32>(7, line, &line[5..8]).unwrap_err();
+        assert_eq!(err.line, 7);
+        assert_eq!(err.column, 6);
+        assert_eq!(err.text, "3x4");
+    }
+
+    #[test]
+    fn test_display_underlines_text() {
+        let line = "forward 5x";
+        let err = number::<u32>(12, line, &line[8..]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\
+line 12, column 9: expected a number, found `5x`
+   |
+12 | forward 5x
+   |         ^^"
+        );
+    }
+
+    #[test]
+    fn test_numbered_lines_skips_blank_lines() {
+        let lines: Vec<_> = numbered_lines("a\n\n  \nb\n").collect();
+        assert_eq!(lines, vec![(1, "a"), (4, "b")]);
+    }
+}
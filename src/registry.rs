@@ -0,0 +1,143 @@
+//! All solved puzzles, so the `aoc` runner can look them up by day.
+
+use std::{any::Any, fmt};
+
+use anyhow::anyhow;
+
+use crate::{day1, day10, day2, day3, day4, day5, day6, day7, day8, day9, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The parsed input of a puzzle, as returned by [`Puzzle::parse`].
+///
+/// Only the puzzle that produced it knows what's inside.
+pub struct ParsedInput(Box<dyn Any>);
+
+/// A [`Solution`] with its types erased, so all days fit into the same list.
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    parse: fn(&str) -> anyhow::Result<ParsedInput>,
+    part1: fn(&ParsedInput) -> anyhow::Result<String>,
+    part2: fn(&ParsedInput) -> anyhow::Result<String>,
+}
+
+impl Puzzle {
+    pub const fn new<S>(day: u8, title: &'static str) -> Self
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        Self {
+            day,
+            title,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> anyhow::Result<ParsedInput> {
+        (self.parse)(input)
+    }
+
+    pub fn solve_parsed(&self, part: Part, input: &ParsedInput) -> anyhow::Result<String> {
+        match part {
+            Part::One => (self.part1)(input),
+            Part::Two => (self.part2)(input),
+        }
+    }
+
+    pub fn solve(&self, part: Part, input: &str) -> anyhow::Result<String> {
+        let input = self.parse(input)?;
+        self.solve_parsed(part, &input)
+    }
+
+    /// Where the puzzle input is expected by default, relative to the repository root.
+    pub fn default_input_path(&self) -> String {
+        format!("./input/day{}.txt", self.day)
+    }
+}
+
+fn parse<S>(input: &str) -> anyhow::Result<ParsedInput>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    Ok(ParsedInput(Box::new(S::parse(input)?)))
+}
+
+fn part1<S>(input: &ParsedInput) -> anyhow::Result<String>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part1(downcast::<S>(input)?).map(|answer| answer.to_string())
+}
+
+fn part2<S>(input: &ParsedInput) -> anyhow::Result<String>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    S::part2(downcast::<S>(input)?).map(|answer| answer.to_string())
+}
+
+fn downcast<S>(input: &ParsedInput) -> anyhow::Result<&S::Input>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    input
+        .0
+        .downcast_ref()
+        .ok_or_else(|| anyhow!("input was parsed by a different puzzle"))
+}
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new::<day1::Day1>(1, "Sonar Sweep"),
+    Puzzle::new::<day2::Day2>(2, "Dive!"),
+    Puzzle::new::<day3::Day3>(3, "Binary Diagnostic"),
+    Puzzle::new::<day4::Day4>(4, "Giant Squid"),
+    Puzzle::new::<day5::Day5>(5, "Hydrothermal Venture"),
+    Puzzle::new::<day6::Day6>(6, "Lanternfish"),
+    Puzzle::new::<day7::Day7>(7, "The Treachery of Whales"),
+    Puzzle::new::<day8::Day8>(8, "Seven Segment Search"),
+    Puzzle::new::<day9::Day9>(9, "Smoke Basin"),
+    Puzzle::new::<day10::Day10>(10, "Syntax Scoring"),
+];
+
+pub fn puzzle(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let days: Vec<u8> = PUZZLES.iter().map(|p| p.day).collect();
+        assert_eq!(days, (1..=10).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_input_of_another_puzzle_is_rejected() {
+        let input = puzzle(1).unwrap().parse("1\n2\n3").unwrap();
+        let err = puzzle(7).unwrap().solve_parsed(Part::One, &input);
+        assert!(err.is_err());
+    }
+}
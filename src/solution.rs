@@ -0,0 +1,13 @@
+use std::fmt::Display;
+
+/// A puzzle solution: parses the puzzle input once and solves both parts of the puzzle
+/// on the parsed input.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input>;
+    fn part1(input: &Self::Input) -> anyhow::Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> anyhow::Result<Self::Answer2>;
+}
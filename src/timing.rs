@@ -0,0 +1,122 @@
+//! Wall-clock timings of all puzzles, a quick alternative to the Criterion benchmarks.
+
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::{Serialize, Serializer};
+
+use crate::registry::{Part, Puzzle};
+
+#[derive(Debug, Serialize)]
+pub struct PuzzleTiming {
+    pub day: u8,
+    pub title: &'static str,
+    #[serde(rename = "parse_us", serialize_with = "micros")]
+    pub parse: Duration,
+    #[serde(rename = "part1_us", serialize_with = "micros")]
+    pub part1: Duration,
+    #[serde(rename = "part2_us", serialize_with = "micros")]
+    pub part2: Duration,
+}
+
+impl PuzzleTiming {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Parses the input and solves both parts once, measuring the time each phase takes.
+///
+/// Fails if the input can't be parsed or a part can't be solved.
+pub fn time_puzzle(puzzle: &Puzzle, input: &str) -> anyhow::Result<PuzzleTiming> {
+    let start = Instant::now();
+    let parsed = puzzle.parse(input).context("failed to parse the input")?;
+    let parse = start.elapsed();
+
+    let time_part = |part| -> anyhow::Result<Duration> {
+        let start = Instant::now();
+        puzzle
+            .solve_parsed(part, &parsed)
+            .with_context(|| format!("failed to solve part {}", part))?;
+        Ok(start.elapsed())
+    };
+    let part1 = time_part(Part::One)?;
+    let part2 = time_part(Part::Two)?;
+
+    Ok(PuzzleTiming {
+        day: puzzle.day,
+        title: puzzle.title,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimingReport {
+    pub puzzles: Vec<PuzzleTiming>,
+    #[serde(rename = "total_us", serialize_with = "micros")]
+    pub total: Duration,
+}
+
+/// Times all given puzzles on their default input files.
+pub fn time_puzzles(puzzles: &[Puzzle]) -> anyhow::Result<TimingReport> {
+    let puzzles = puzzles
+        .iter()
+        .map(|puzzle| {
+            let path = puzzle.default_input_path();
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input file {}", path))?;
+            time_puzzle(puzzle, &input)
+                .with_context(|| format!("failed to time day {} on {}", puzzle.day, path))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let total = puzzles.iter().map(PuzzleTiming::total).sum();
+    Ok(TimingReport { puzzles, total })
+}
+
+fn micros<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1e6)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry::puzzle;
+
+    #[test]
+    fn test_unsolvable_part_fails() {
+        // There's no BINGO on a board without any drawn number on it:
+        let input = "\
+            99
+            1 2 3 4 5
+            6 7 8 9 10
+            11 12 13 14 15
+            16 17 18 19 20
+            21 22 23 24 25
+        ";
+        let err = time_puzzle(puzzle(4).unwrap(), input).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "failed to solve part 1: no board has BINGO"
+        );
+    }
+
+    #[test]
+    fn test_json_uses_microseconds() {
+        let timing = PuzzleTiming {
+            day: 6,
+            title: "Lanternfish",
+            parse: Duration::from_micros(12),
+            part1: Duration::from_nanos(1500),
+            part2: Duration::from_micros(3),
+        };
+        assert_eq!(
+            serde_json::to_string(&timing).unwrap(),
+            r#"{"day":6,"title":"Lanternfish","parse_us":12.0,"part1_us":1.5,"part2_us":3.0}"#
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use aoc::{
+    answers::{self, Answers},
+    registry::PUZZLES,
+};
+
+/// Makes sure all answers on the real puzzle inputs still match the recorded ones.
+#[test]
+fn test_recorded_answers_are_unchanged() {
+    let answers = Answers::load(answers::DEFAULT_PATH).unwrap();
+    let regressions: Vec<String> = answers::check(PUZZLES, &answers)
+        .unwrap()
+        .into_iter()
+        .filter(|check| check.outcome.is_regression())
+        .map(|check| format!("day {} part {}: {}", check.day, check.part, check.outcome))
+        .collect();
+    assert!(regressions.is_empty(), "{:#?}", regressions);
+}